@@ -4,6 +4,7 @@ use std::time::Instant;
 
 use floating_duration::{TimeAsFloat, TimeFormat};
 
+#[allow(unknown_lints, renamed_and_removed_lints, unnecessary_fold)]
 fn main() {
     let start = Instant::now();
 
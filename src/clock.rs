@@ -0,0 +1,160 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Borrow;
+use std::fmt::{Display, Error as FormatError, Formatter, Write};
+use std::time::Duration;
+
+use exact;
+use format::pad;
+use {RoundingMode, TimeUnit};
+
+/// A formatting newtype which prints a duration like a stopwatch,
+/// e.g. `01:02:03.456`.
+///
+/// # Behaviour
+///
+/// Durations below an hour are printed as `MM:SS.fff`, longer ones
+/// as `HH:MM:SS.fff`; the hours aren't wrapped at 24 unless the
+/// [day prefix] is enabled (`2d 03:04:05.000`). The [fraction digits]
+/// (3 by default) can be overridden with the [precision], e.g. `{:.0}`.
+///
+/// Width, fill, alignment and the `+` flag are honored
+/// like for [`TimeFormat`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::ClockFormat;
+///
+/// let dur = Duration::new(3_723, 456_000_000);
+/// assert_eq!(format!("{}", ClockFormat::new(dur)), "01:02:03.456");
+/// assert_eq!(format!("{:.1}", ClockFormat::new(dur)), "01:02:03.5");
+///
+/// let dur = Duration::new(65, 250_000_000);
+/// assert_eq!(format!("{}", ClockFormat::new(dur)), "01:05.250");
+/// assert_eq!(format!("{}", ClockFormat::new(dur).always_hours(true)), "00:01:05.250");
+/// assert_eq!(format!("{}", ClockFormat::new(dur).fraction_digits(0)), "01:05");
+///
+/// let dur = Duration::new(2 * 86_400 + 3 * 3_600 + 4 * 60 + 5, 0);
+/// assert_eq!(format!("{:.0}", ClockFormat::new(dur)), "51:04:05");
+/// assert_eq!(format!("{:.0}", ClockFormat::new(dur).days(true)), "2d 03:04:05");
+/// ```
+///
+/// [day prefix]: #method.days
+/// [fraction digits]: #method.fraction_digits
+/// [precision]: https://doc.rust-lang.org/stable/std/fmt/#precision
+/// [`TimeFormat`]: struct.TimeFormat.html
+#[derive(Clone, Copy, Debug)]
+pub struct ClockFormat<T: Borrow<Duration>> {
+    dur: T,
+    fraction_digits: usize,
+    days: bool,
+    always_hours: bool,
+    rounding: RoundingMode,
+}
+
+impl<T: Borrow<Duration>> ClockFormat<T> {
+    /// Creates a new clock format with 3 fraction digits.
+    pub fn new(dur: T) -> Self {
+        ClockFormat {
+            dur,
+            fraction_digits: 3,
+            days: false,
+            always_hours: false,
+            rounding: RoundingMode::HalfUp,
+        }
+    }
+
+    /// Sets the number of digits after the seconds;
+    /// `0` leaves out the decimal point.
+    pub fn fraction_digits(mut self, digits: usize) -> Self {
+        self.fraction_digits = digits;
+
+        self
+    }
+
+    /// Prefixes durations of a day or longer with the
+    /// number of days (e.g. `2d 03:04:05.000`).
+    pub fn days(mut self, days: bool) -> Self {
+        self.days = days;
+
+        self
+    }
+
+    /// Always shows the hours, even for durations below an hour.
+    pub fn always_hours(mut self, always: bool) -> Self {
+        self.always_hours = always;
+
+        self
+    }
+
+    /// Sets how the seconds are rounded to the fraction digits,
+    /// which defaults to `RoundingMode::HalfUp`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use floating_duration::{ClockFormat, RoundingMode};
+    ///
+    /// let clock = ClockFormat::new(Duration::new(59, 999_999_999));
+    /// assert_eq!(format!("{:.0}", clock), "01:00");
+    /// assert_eq!(format!("{:.0}", clock.rounding(RoundingMode::Truncate)), "00:59");
+    /// ```
+    pub fn rounding(mut self, mode: RoundingMode) -> Self {
+        self.rounding = mode;
+
+        self
+    }
+
+    fn write_unpadded<W: Write>(
+        &self,
+        w: &mut W,
+        precision: Option<usize>,
+    ) -> Result<(), FormatError> {
+        let digits = precision.unwrap_or(self.fraction_digits);
+        let nanos = exact::total_nanos(self.dur.borrow());
+        let (scaled, places) =
+            exact::round_decimal(nanos, TimeUnit::Secs.nanos(), digits, self.rounding);
+        let pow = 10u128.pow(places as u32);
+        let secs = scaled / pow;
+        let fraction = scaled % pow;
+
+        let (days, hours) = if self.days {
+            (secs / 86_400, secs / 3_600 % 24)
+        } else {
+            (0, secs / 3_600)
+        };
+        let minutes = secs / 60 % 60;
+        let secs = secs % 60;
+
+        if days > 0 {
+            write!(w, "{}d {:02}:", days, hours)?;
+        } else if hours > 0 || self.always_hours {
+            write!(w, "{:02}:", hours)?;
+        }
+        write!(w, "{:02}:{:02}", minutes, secs)?;
+
+        if digits > 0 {
+            exact::write_fraction(w, fraction, places, Some(digits))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Borrow<Duration>> Display for ClockFormat<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let mut buf = String::new();
+        self.write_unpadded(&mut buf, f.precision())?;
+
+        pad(f, &buf)
+    }
+}
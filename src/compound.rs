@@ -0,0 +1,229 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Borrow;
+use std::cmp;
+use std::fmt::{Display, Error as FormatError, Formatter, Write};
+use std::time::Duration;
+
+use exact;
+use format::pad;
+use unit::UNITS;
+use {RoundingMode, TimeUnit};
+
+/// A formatting newtype which splits a duration into
+/// multiple units, e.g. `1h 02m 3.456s`.
+///
+/// # Behaviour
+///
+/// The duration is split into every unit from the [largest unit]
+/// (days by default) down to the [smallest unit] (seconds by default).
+/// Leading zero components are omitted; the smallest unit is printed
+/// with up to 3 decimal places, or exactly as many as the [precision]
+/// specifies. Components between the first one and the smallest unit
+/// are zero-padded.
+///
+/// With the [alternate flag] `{:#}`, full unit names are used
+/// instead (e.g. `1 hour, 2 minutes, 3.456 seconds`).
+///
+/// Width, fill, alignment and the `+` flag are honored
+/// like for [`TimeFormat`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::{CompoundFormat, TimeUnit};
+///
+/// let dur = Duration::new(3_723, 456_000_000);
+/// assert_eq!(format!("{}", CompoundFormat::new(dur)), "1h 02m 3.456s");
+/// assert_eq!(
+///     format!("{:#}", CompoundFormat::new(dur)),
+///     "1 hour, 2 minutes, 3.456 seconds"
+/// );
+///
+/// let dur = Duration::new(2 * 86_400 + 3 * 3_600 + 17, 0);
+/// let format = CompoundFormat::new(dur).max_components(2);
+/// assert_eq!(format!("{}", format), "2d 03h");
+///
+/// let format = CompoundFormat::new(dur).skip_zeros(true);
+/// assert_eq!(format!("{}", format), "2d 03h 17s");
+/// assert_eq!(format!("{:.0}", CompoundFormat::new(Duration::new(59, 600_000_000))), "1m 0s");
+///
+/// let dur = Duration::new(0, 12_345_678);
+/// let format = CompoundFormat::new(dur)
+///     .largest_unit(TimeUnit::Millis)
+///     .smallest_unit(TimeUnit::Micros);
+/// assert_eq!(format!("{:.1}", format), "12ms 345.7µs");
+/// ```
+///
+/// [largest unit]: #method.largest_unit
+/// [smallest unit]: #method.smallest_unit
+/// [precision]: https://doc.rust-lang.org/stable/std/fmt/#precision
+/// [alternate flag]: https://doc.rust-lang.org/stable/std/fmt/#sign0
+/// [`TimeFormat`]: struct.TimeFormat.html
+#[derive(Clone, Copy, Debug)]
+pub struct CompoundFormat<T: Borrow<Duration>> {
+    dur: T,
+    largest: TimeUnit,
+    smallest: TimeUnit,
+    skip_zeros: bool,
+    max_components: Option<usize>,
+    rounding: RoundingMode,
+}
+
+impl<T: Borrow<Duration>> CompoundFormat<T> {
+    /// Creates a new compound format from days down to seconds.
+    pub fn new(dur: T) -> Self {
+        CompoundFormat {
+            dur,
+            largest: TimeUnit::Days,
+            smallest: TimeUnit::Secs,
+            skip_zeros: false,
+            max_components: None,
+            rounding: RoundingMode::HalfUp,
+        }
+    }
+
+    /// Sets the largest unit; larger amounts are expressed in it
+    /// (e.g. `50h` instead of `2d 02h`).
+    pub fn largest_unit(mut self, unit: TimeUnit) -> Self {
+        self.largest = unit;
+
+        self
+    }
+
+    /// Sets the smallest unit, which carries the decimal places.
+    pub fn smallest_unit(mut self, unit: TimeUnit) -> Self {
+        self.smallest = unit;
+
+        self
+    }
+
+    /// Omits components which are zero (e.g. `1h 5s` instead of `1h 00m 5s`).
+    pub fn skip_zeros(mut self, skip: bool) -> Self {
+        self.skip_zeros = skip;
+
+        self
+    }
+
+    /// Limits the output to the `n` most significant components,
+    /// truncating the rest (e.g. `2d 03h` instead of `2d 03h 04m 5s`).
+    /// Components left out by [`skip_zeros`] don't count.
+    ///
+    /// [`skip_zeros`]: #method.skip_zeros
+    pub fn max_components(mut self, n: usize) -> Self {
+        self.max_components = Some(cmp::max(n, 1));
+
+        self
+    }
+
+    /// Sets how the smallest unit is rounded to the decimal places,
+    /// which defaults to `RoundingMode::HalfUp`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use floating_duration::{CompoundFormat, RoundingMode};
+    ///
+    /// let format = CompoundFormat::new(Duration::new(3_599, 999_900_000));
+    /// assert_eq!(format!("{}", format), "1h 00m 0s");
+    /// assert_eq!(format!("{}", format.rounding(RoundingMode::Truncate)), "59m 59.999s");
+    /// ```
+    pub fn rounding(mut self, mode: RoundingMode) -> Self {
+        self.rounding = mode;
+
+        self
+    }
+
+    fn write_unpadded<W: Write>(
+        &self,
+        w: &mut W,
+        alternate: bool,
+        precision: Option<usize>,
+    ) -> Result<(), FormatError> {
+        let smallest = self.smallest;
+        let largest = cmp::max(self.largest, smallest);
+
+        // Round to the printed decimal places first,
+        // so e.g. 59.9996s carries over into the minutes.
+        let (mut rest, fraction) = exact::divide_decimal(
+            exact::total_nanos(self.dur.borrow()),
+            smallest.nanos() as u128,
+            precision.unwrap_or(3),
+            self.rounding,
+        );
+
+        let mut components = Vec::new();
+        for &unit in UNITS.iter().rev() {
+            if unit < smallest || unit > largest {
+                continue;
+            }
+
+            let count = (unit.nanos() / smallest.nanos()) as u128;
+            components.push((unit, rest / count));
+            rest %= count;
+        }
+
+        let first = components
+            .iter()
+            .position(|&(_, count)| count > 0)
+            .unwrap_or(components.len() - 1);
+        let limit = self.max_components.unwrap_or(components.len());
+
+        let mut written = 0;
+        for (i, &(unit, count)) in components.iter().enumerate().skip(first) {
+            if written == limit {
+                break;
+            }
+
+            let has_fraction = unit == smallest;
+            let is_zero = count == 0 && (!has_fraction || fraction.iter().all(|&d| d == 0));
+            if self.skip_zeros && is_zero && (written > 0 || i + 1 < components.len()) {
+                continue;
+            }
+
+            let mut value = String::new();
+            if alternate || written == 0 || has_fraction {
+                write!(value, "{}", count)?;
+            } else {
+                let width = count_width(components[i - 1].0, unit);
+                write!(value, "{:01$}", count, width)?;
+            }
+            if has_fraction {
+                exact::write_digits(&mut value, &fraction, precision)?;
+            }
+
+            match (alternate, written > 0) {
+                (false, false) => write!(w, "{}{}", value, unit.abbreviation())?,
+                (false, true) => write!(w, " {}{}", value, unit.abbreviation())?,
+                (true, false) => write!(w, "{} {}", value, unit.name_for(&value))?,
+                (true, true) => write!(w, ", {} {}", value, unit.name_for(&value))?,
+            }
+
+            written += 1;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Borrow<Duration>> Display for CompoundFormat<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let mut buf = String::new();
+        self.write_unpadded(&mut buf, f.alternate(), f.precision())?;
+
+        pad(f, &buf)
+    }
+}
+
+/// The number of digits needed for `unit` below `larger`, e.g. 2 for minutes.
+fn count_width(larger: TimeUnit, unit: TimeUnit) -> usize {
+    (larger.nanos() / unit.nanos() - 1).to_string().len()
+}
@@ -0,0 +1,354 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Exact integer arithmetic on durations.
+
+use std::cmp;
+use std::fmt::{Error as FormatError, Write};
+use std::time::Duration;
+
+use {RoundingMode, TimeUnit};
+
+/// Returns the total number of nanoseconds in `dur`.
+pub fn total_nanos(dur: &Duration) -> u128 {
+    dur.as_secs() as u128 * NANOS_PER_SEC + dur.subsec_nanos() as u128
+}
+
+/// The largest number of nanoseconds a `Duration` can hold.
+const MAX_NANOS: u128 = (::std::u64::MAX as u128) * NANOS_PER_SEC + (NANOS_PER_SEC - 1);
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Returns the duration of `nanos` nanoseconds, if it's in range.
+pub fn duration_from_nanos(nanos: u128) -> Option<Duration> {
+    if nanos > MAX_NANOS {
+        return None;
+    }
+
+    Some(Duration::new(
+        (nanos / NANOS_PER_SEC) as u64,
+        (nanos % NANOS_PER_SEC) as u32,
+    ))
+}
+
+/// Rounds `nanos / unit_nanos` to `decimals` decimal places, or fewer
+/// if the unit doesn't have that many places above nanosecond resolution.
+///
+/// Returns the rounded value scaled by `10^places`, and `places`.
+pub fn round_decimal(
+    nanos: u128,
+    unit_nanos: u64,
+    decimals: usize,
+    mode: RoundingMode,
+) -> (u128, usize) {
+    round_ticks(nanos, unit_nanos as u128, decimals, mode)
+}
+
+/// Like `round_decimal`, but for a value and a unit counted
+/// in ticks of any (decimal) resolution.
+///
+/// For a unit which isn't a power of ten (e.g. minutes), every place is
+/// computed by exact division; only as many places as fit into a `u128`
+/// are kept.
+pub fn round_ticks(
+    ticks: u128,
+    unit_ticks: u128,
+    decimals: usize,
+    mode: RoundingMode,
+) -> (u128, usize) {
+    let zeros = trailing_zeros(unit_ticks);
+    // Past the trailing zeros of a power of ten, all digits are zero.
+    let mut places = if unit_ticks == 10u128.pow(zeros as u32) {
+        cmp::min(decimals, zeros)
+    } else {
+        cmp::min(decimals, MAX_PLACES)
+    };
+
+    loop {
+        if let Some(scaled) = divide_places(ticks, unit_ticks, places, mode) {
+            return (scaled, places);
+        }
+        places -= 1;
+    }
+}
+
+/// The most decimal places a scaled `u128` can have.
+const MAX_PLACES: usize = 38;
+
+/// Divides `num` by `den` with `places` decimal places by long division,
+/// returning the quotient scaled by `10^places`, or `None` if it overflows.
+fn divide_places(num: u128, den: u128, places: usize, mode: RoundingMode) -> Option<u128> {
+    let mut quotient = num / den;
+    let mut remainder = num % den;
+    for _ in 0..places {
+        let (digit, rest) = times_ten(remainder, den);
+        quotient = quotient.checked_mul(10)?.checked_add(digit)?;
+        remainder = rest;
+    }
+
+    if round_up(quotient, remainder, den, mode) {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Returns the quotient and remainder of `10 * remainder / den`
+/// for `remainder < den < 2^127`, without overflowing.
+fn times_ten(remainder: u128, den: u128) -> (u128, u128) {
+    if let Some(remainder) = remainder.checked_mul(10) {
+        return (remainder / den, remainder % den);
+    }
+
+    let mut digit = 0;
+    let mut rest = 0;
+    for _ in 0..10 {
+        rest += remainder;
+        if rest >= den {
+            rest -= den;
+            digit += 1;
+        }
+    }
+
+    (digit, rest)
+}
+
+/// Divides `num` by `den`, rounding the quotient as specified by `mode`.
+pub fn divide(num: u128, den: u128, mode: RoundingMode) -> u128 {
+    let quotient = num / den;
+
+    if round_up(quotient, num % den, den, mode) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+/// Whether `quotient` is rounded up, given the `remainder` of the division by `den`.
+fn round_up(quotient: u128, remainder: u128, den: u128, mode: RoundingMode) -> bool {
+    match mode {
+        RoundingMode::HalfUp => remainder >= den - remainder,
+        RoundingMode::HalfEven => {
+            remainder > den - remainder || (remainder == den - remainder && quotient % 2 == 1)
+        }
+        RoundingMode::Truncate | RoundingMode::Floor => false,
+        RoundingMode::Ceil => remainder > 0,
+    }
+}
+
+/// The number of decimal places a unit can have
+/// without going below nanoseconds.
+pub fn max_decimals(unit_nanos: u64) -> usize {
+    trailing_zeros(unit_nanos as u128)
+}
+
+/// The number of trailing decimal zeros of `n`, which must not be zero.
+fn trailing_zeros(mut n: u128) -> usize {
+    let mut zeros = 0;
+    while n % 10 == 0 {
+        n /= 10;
+        zeros += 1;
+    }
+
+    zeros
+}
+
+/// An exact, non-negative time of `ticks * 10^-scale` nanoseconds.
+///
+/// A `Duration` has a scale of zero; a larger one keeps
+/// fractions of a nanosecond.
+#[derive(Clone, Copy, Debug)]
+pub struct Ticks {
+    pub ticks: u128,
+    pub scale: u32,
+}
+
+impl Ticks {
+    /// The exact time of `nanos` nanoseconds.
+    pub fn from_nanos(nanos: u128) -> Self {
+        Ticks {
+            ticks: nanos,
+            scale: 0,
+        }
+    }
+
+    /// The number of ticks in one `unit`.
+    pub fn per_unit(&self, unit: TimeUnit) -> u128 {
+        unit.nanos() as u128 * 10u128.pow(self.scale)
+    }
+}
+
+/// Writes `scaled / 10^places` as a decimal number, see `write_fraction`.
+pub fn write_decimal<W: Write>(
+    w: &mut W,
+    scaled: u128,
+    places: usize,
+    precision: Option<usize>,
+) -> Result<(), FormatError> {
+    let pow = 10u128.pow(places as u32);
+
+    write!(w, "{}", scaled / pow)?;
+    write_fraction(w, scaled % pow, places, precision)
+}
+
+/// Writes the `places` digits of `fraction`, followed by zeros up to
+/// `precision`, or with trailing zeros stripped if there is no precision.
+pub fn write_fraction<W: Write>(
+    w: &mut W,
+    fraction: u128,
+    places: usize,
+    precision: Option<usize>,
+) -> Result<(), FormatError> {
+    let digits: Vec<u8> = if places > 0 {
+        format!("{:01$}", fraction, places)
+            .bytes()
+            .map(|digit| digit - b'0')
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    write_digits(w, &digits, precision)
+}
+
+/// Writes `num / den` rounded to `decimals` decimal places, like
+/// `write_decimal`; every digit is computed by long division, so
+/// there can be any number of places.
+pub fn write_quotient<W: Write>(
+    w: &mut W,
+    num: u128,
+    den: u128,
+    decimals: usize,
+    mode: RoundingMode,
+    precision: Option<usize>,
+) -> Result<(), FormatError> {
+    let (int, fraction) = divide_decimal(num, den, decimals, mode);
+
+    write!(w, "{}", int)?;
+    write_digits(w, &fraction, precision)
+}
+
+/// Writes the decimal `digits` of a fraction as `write_fraction` does.
+pub fn write_digits<W: Write>(
+    w: &mut W,
+    digits: &[u8],
+    precision: Option<usize>,
+) -> Result<(), FormatError> {
+    let (digits, zeros) = match precision {
+        Some(precision) => (digits, precision.saturating_sub(digits.len())),
+        None => {
+            let len = digits
+                .iter()
+                .rposition(|&digit| digit != 0)
+                .map_or(0, |i| i + 1);
+
+            (&digits[..len], 0)
+        }
+    };
+    if digits.is_empty() && zeros == 0 {
+        return Ok(());
+    }
+
+    w.write_char('.')?;
+    for &digit in digits {
+        w.write_char((b'0' + digit) as char)?;
+    }
+    for _ in 0..zeros {
+        w.write_char('0')?;
+    }
+
+    Ok(())
+}
+
+/// Divides `num` by `den` with `decimals` decimal places, rounding as
+/// specified by `mode`; returns the integer part and the decimal digits.
+pub fn divide_decimal(
+    num: u128,
+    den: u128,
+    decimals: usize,
+    mode: RoundingMode,
+) -> (u128, Vec<u8>) {
+    let mut int = num / den;
+    let mut remainder = num % den;
+    let mut fraction = Vec::with_capacity(decimals);
+    for _ in 0..decimals {
+        let (digit, rest) = times_ten(remainder, den);
+        fraction.push(digit as u8);
+        remainder = rest;
+    }
+
+    let last = fraction.last().map_or(int, |&digit| digit as u128);
+    if round_up(last, remainder, den, mode) {
+        let mut carry = true;
+        for digit in fraction.iter_mut().rev() {
+            if *digit == 9 {
+                *digit = 0;
+            } else {
+                *digit += 1;
+                carry = false;
+                break;
+            }
+        }
+        if carry {
+            int += 1;
+        }
+    }
+
+    (int, fraction)
+}
+
+/// Returns the `f64` nearest to `num / den` (ties to even).
+pub fn ratio_to_f64(num: u128, den: u64) -> f64 {
+    let (mantissa, exp) = round_ratio(num, den, 53);
+
+    mantissa as f64 * f64::from_bits(((exp + 1023) as u64) << 52)
+}
+
+/// Returns the `f32` nearest to `num / den` (ties to even).
+pub fn ratio_to_f32(num: u128, den: u64) -> f32 {
+    let (mantissa, exp) = round_ratio(num, den, 24);
+
+    mantissa as f32 * f32::from_bits(((exp + 127) as u32) << 23)
+}
+
+/// Rounds `num / den` to a `bits`-bit mantissa, returning
+/// `(mantissa, exp)` with `mantissa * 2^exp` being the rounded value.
+///
+/// The mantissa might be `2^bits` if rounding carried.
+fn round_ratio(num: u128, den: u64, bits: i32) -> (u64, i32) {
+    if num == 0 {
+        return (0, 0);
+    }
+
+    let den = den as u128;
+    let num_len = 128 - num.leading_zeros() as i32;
+    let den_len = 128 - den.leading_zeros() as i32;
+
+    // Scale the operands so the quotient has `bits + 2` or `bits + 3`
+    // significant bits; the rest of the value only matters for
+    // deciding ties.
+    let shift = bits + 2 - (num_len - den_len);
+    let (num, den) = if shift >= 0 {
+        (num << shift, den)
+    } else {
+        (num, den << -shift)
+    };
+
+    let quotient = num / den;
+    let sticky = num % den != 0;
+
+    let extra = 128 - quotient.leading_zeros() as i32 - bits;
+    let mantissa = (quotient >> extra) as u64;
+    let rest = quotient & ((1 << extra) - 1);
+    let half = 1 << (extra - 1);
+
+    let round_up = rest > half || (rest == half && (sticky || mantissa & 1 == 1));
+    let mantissa = if round_up { mantissa + 1 } else { mantissa };
+
+    (mantissa, extra - shift)
+}
@@ -0,0 +1,125 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Borrow;
+use std::fmt::{Display, Error as FormatError, Formatter, Write};
+use std::time::Duration;
+
+use exact;
+use format::pad;
+use {RoundingMode, TimeUnit};
+
+/// A formatting newtype which prints a duration in seconds with
+/// all nine decimal places, e.g. `4.123456789s`.
+///
+/// The output is computed from `as_secs()` and `subsec_nanos()` with
+/// integer arithmetic only, so it is exact and the same on every
+/// platform. A [precision] of 0 to 8 rounds to that many decimal places,
+/// half up unless a different [rounding mode] is set; a larger one
+/// appends zeros.
+///
+/// With the [alternate flag] `{:#}`, the unit is spelled out.
+/// Width, fill, alignment and the `+` flag are honored
+/// like for [`TimeFormat`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::ExactFormat;
+///
+/// let dur = Duration::new(4, 123_456_789);
+/// assert_eq!(format!("{}", ExactFormat(dur)), "4.123456789s");
+/// assert_eq!(format!("{:.3}", ExactFormat(dur)), "4.123s");
+/// assert_eq!(format!("{:.0}", ExactFormat(dur)), "4s");
+/// assert_eq!(format!("{:#.1}", ExactFormat(dur)), "4.1 seconds");
+///
+/// let dur = Duration::new(u64::max_value(), 999_999_999);
+/// assert_eq!(format!("{:.2}", ExactFormat(dur)), "18446744073709551616.00s");
+/// ```
+///
+/// [precision]: https://doc.rust-lang.org/stable/std/fmt/#precision
+/// [rounding mode]: #method.rounding
+/// [alternate flag]: https://doc.rust-lang.org/stable/std/fmt/#sign0
+/// [`TimeFormat`]: struct.TimeFormat.html
+#[derive(Clone, Copy, Debug)]
+pub struct ExactFormat<T: Borrow<Duration>>(pub T);
+
+impl<T: Borrow<Duration>> ExactFormat<T> {
+    /// Sets how the seconds are rounded to the printed number of
+    /// decimal places; the default is [`RoundingMode::HalfUp`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use floating_duration::{ExactFormat, RoundingMode};
+    ///
+    /// let dur = Duration::new(4, 987_654_321);
+    /// assert_eq!(format!("{:.2}", ExactFormat(dur)), "4.99s");
+    /// assert_eq!(format!("{:.2}", ExactFormat(dur).rounding(RoundingMode::Truncate)), "4.98s");
+    /// ```
+    ///
+    /// [`RoundingMode::HalfUp`]: enum.RoundingMode.html#variant.HalfUp
+    pub fn rounding(self, mode: RoundingMode) -> RoundedExactFormat<T> {
+        RoundedExactFormat { dur: self.0, mode }
+    }
+}
+
+impl<T: Borrow<Duration>> Display for ExactFormat<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        RoundedExactFormat {
+            dur: self.0.borrow(),
+            mode: RoundingMode::HalfUp,
+        }
+        .fmt(f)
+    }
+}
+
+/// An [`ExactFormat`] with a custom rounding mode, created by
+/// [`ExactFormat::rounding`].
+///
+/// [`ExactFormat`]: struct.ExactFormat.html
+/// [`ExactFormat::rounding`]: struct.ExactFormat.html#method.rounding
+#[derive(Clone, Copy, Debug)]
+pub struct RoundedExactFormat<T> {
+    dur: T,
+    mode: RoundingMode,
+}
+
+impl<T: Borrow<Duration>> RoundedExactFormat<T> {
+    fn write_unpadded<W: Write>(
+        &self,
+        w: &mut W,
+        alternate: bool,
+        precision: Option<usize>,
+    ) -> Result<(), FormatError> {
+        let unit = TimeUnit::Secs;
+        let decimals = precision.unwrap_or(9);
+        let nanos = exact::total_nanos(self.dur.borrow());
+        let (scaled, places) = exact::round_decimal(nanos, unit.nanos(), decimals, self.mode);
+
+        let mut value = String::new();
+        exact::write_decimal(&mut value, scaled, places, Some(decimals))?;
+
+        if !alternate {
+            write!(w, "{}{}", value, unit.abbreviation())
+        } else {
+            write!(w, "{} {}", value, unit.name_for(&value))
+        }
+    }
+}
+
+impl<T: Borrow<Duration>> Display for RoundedExactFormat<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let mut buf = String::new();
+        self.write_unpadded(&mut buf, f.alternate(), f.precision())?;
+
+        pad(f, &buf)
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt::{Display, Error as FormatError, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::time::Duration;
+
+use exact::Ticks;
+use {DurationFromFloat, FromFloatError, SignedDuration, TimeAsFloat, TimeFormat, TimeUnit};
+
+/// A duration in seconds stored as an `f64`, e.g. the result
+/// of averaging or scaling measurements.
+///
+/// Unlike a `Duration`, it can be negative and finer than a
+/// nanosecond. It supports the usual arithmetic and the
+/// [`TimeAsFloat`] conversions.
+///
+/// # Behaviour
+///
+/// It is printed like [`TimeFormat`] prints a `Duration` (also with a
+/// [style]), but values below one nanosecond keep their decimal places
+/// (e.g. `0.35ns`) and negative values get a leading `-`. NaN and
+/// infinities are printed like an `f64`, without a unit, and values
+/// above about 10^29 seconds in scientific notation.
+/// Decimal places beyond 10^-22 nanoseconds may be inexact.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::{FloatDuration, TimeAsFloat, TimeFormat, TimeFormatStyle};
+///
+/// let runs = [Duration::new(0, 1_200), Duration::new(0, 1_500), Duration::new(0, 1_350)];
+/// let mean = runs.iter().map(|&dur| FloatDuration::from(dur)).sum::<FloatDuration>() / 3.0;
+///
+/// assert_eq!(format!("{}", mean), "1.35µs");
+/// assert_eq!(format!("{}", mean / 1_000.0), "1.35ns");
+/// assert_eq!(format!("{}", mean / 10_000.0), "0.135ns");
+/// assert_eq!(format!("{:#.1}", -mean * 2.0), "-2.7 microseconds");
+///
+/// let style = TimeFormatStyle::new().significant_figures(2);
+/// assert_eq!(format!("{}", TimeFormat::with_style(mean / 3.0, style)), "450ns");
+///
+/// assert!(mean > FloatDuration(1e-6));
+/// assert_eq!((mean * 1e6).to_duration(), Ok(Duration::new(1, 350_000_000)));
+/// assert_eq!(FloatDuration(0.25).as_fractional_millis(), 250.0);
+/// ```
+///
+/// [`TimeAsFloat`]: trait.TimeAsFloat.html
+/// [`TimeFormat`]: struct.TimeFormat.html
+/// [style]: struct.TimeFormat.html#method.with_style
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct FloatDuration(pub f64);
+
+impl FloatDuration {
+    /// Creates a duration of `value` in the given `unit`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use floating_duration::{FloatDuration, TimeUnit};
+    ///
+    /// assert_eq!(FloatDuration::new(1.5, TimeUnit::Minutes), FloatDuration(90.0));
+    /// ```
+    pub fn new(value: f64, unit: TimeUnit) -> Self {
+        FloatDuration(value * unit.nanos() as f64 / 1e9)
+    }
+
+    /// Converts the duration to a `Duration`, rounded
+    /// to the nearest nanosecond.
+    ///
+    /// Fails if the value is NaN, infinite, negative or too large,
+    /// like [`DurationFromFloat::from_fractional_secs`].
+    ///
+    /// [`DurationFromFloat::from_fractional_secs`]: trait.DurationFromFloat.html#tymethod.from_fractional_secs
+    pub fn to_duration(self) -> Result<Duration, FromFloatError> {
+        Duration::from_fractional_secs(self.0)
+    }
+}
+
+impl From<Duration> for FloatDuration {
+    fn from(dur: Duration) -> Self {
+        FloatDuration(dur.as_fractional_secs())
+    }
+}
+
+impl From<SignedDuration> for FloatDuration {
+    fn from(dur: SignedDuration) -> Self {
+        FloatDuration(dur.as_fractional_secs())
+    }
+}
+
+impl Add for FloatDuration {
+    type Output = FloatDuration;
+
+    fn add(self, rhs: FloatDuration) -> FloatDuration {
+        FloatDuration(self.0 + rhs.0)
+    }
+}
+
+impl Sub for FloatDuration {
+    type Output = FloatDuration;
+
+    fn sub(self, rhs: FloatDuration) -> FloatDuration {
+        FloatDuration(self.0 - rhs.0)
+    }
+}
+
+impl Mul<f64> for FloatDuration {
+    type Output = FloatDuration;
+
+    fn mul(self, rhs: f64) -> FloatDuration {
+        FloatDuration(self.0 * rhs)
+    }
+}
+
+impl Div<f64> for FloatDuration {
+    type Output = FloatDuration;
+
+    fn div(self, rhs: f64) -> FloatDuration {
+        FloatDuration(self.0 / rhs)
+    }
+}
+
+/// Returns the ratio of two durations.
+impl Div for FloatDuration {
+    type Output = f64;
+
+    fn div(self, rhs: FloatDuration) -> f64 {
+        self.0 / rhs.0
+    }
+}
+
+impl Neg for FloatDuration {
+    type Output = FloatDuration;
+
+    fn neg(self) -> FloatDuration {
+        FloatDuration(-self.0)
+    }
+}
+
+impl Sum for FloatDuration {
+    fn sum<I: Iterator<Item = FloatDuration>>(iter: I) -> FloatDuration {
+        FloatDuration(iter.map(|dur| dur.0).sum())
+    }
+}
+
+impl<'a> Sum<&'a FloatDuration> for FloatDuration {
+    fn sum<I: Iterator<Item = &'a FloatDuration>>(iter: I) -> FloatDuration {
+        iter.cloned().sum()
+    }
+}
+
+impl TimeAsFloat for FloatDuration {
+    fn as_fractional_secs(&self) -> f64 {
+        self.0
+    }
+
+    fn as_fractional_millis(&self) -> f64 {
+        self.0 * 1e3
+    }
+
+    fn as_fractional_micros(&self) -> f64 {
+        self.0 * 1e6
+    }
+
+    fn as_fractional_nanos(&self) -> f64 {
+        self.0 * 1e9
+    }
+}
+
+impl Display for FloatDuration {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        TimeFormat(*self).fmt(f)
+    }
+}
+
+/// The number of decimal places below a nanosecond that are kept
+/// exactly; one more is a sticky digit standing for any rest.
+const MAX_SCALE: u32 = 23;
+
+/// Returns the magnitude of `secs` as an exact number of ticks,
+/// or `None` if it's not finite or too large.
+///
+/// The value is taken from the shortest decimal which parses back to
+/// `secs` (e.g. `1.5e-9`), so a float converted from a `Duration` is
+/// printed like the duration itself.
+pub fn exact_abs(secs: f64) -> Option<Ticks> {
+    if !secs.is_finite() {
+        return None;
+    }
+
+    let repr = format!("{:e}", secs.abs());
+    let e = repr.find('e')?;
+    let mut digits: u128 = 0;
+    let mut len = 0;
+    for digit in repr[..e].bytes().filter(|&b| b != b'.') {
+        digits = digits * 10 + (digit - b'0') as u128;
+        len += 1;
+    }
+    // The value is `digits * 10^exp` nanoseconds.
+    let exp = repr[e + 1..].parse::<i32>().ok()? - (len - 1) + 9;
+
+    if exp >= 0 {
+        return digits
+            .checked_mul(pow10(exp as u32)?)
+            .map(Ticks::from_nanos);
+    }
+    let scale = -exp as u32;
+    if scale <= MAX_SCALE + 1 {
+        return Some(Ticks {
+            ticks: digits,
+            scale,
+        });
+    }
+
+    // Replacing the rest by a digit that only tells whether it's zero
+    // still rounds the same way to up to `MAX_SCALE - 1` places.
+    let (kept, rest) = match pow10(scale - MAX_SCALE) {
+        Some(pow) => (digits / pow, digits % pow),
+        None => (0, digits),
+    };
+
+    Some(Ticks {
+        ticks: kept * 10 + if rest > 0 { 1 } else { 0 },
+        scale: MAX_SCALE + 1,
+    })
+}
+
+/// Returns `10^exp`, if it fits into a `u128`.
+fn pow10(exp: u32) -> Option<u128> {
+    if exp <= 38 {
+        Some(10u128.pow(exp))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,700 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Borrow;
+use std::cmp;
+use std::fmt::{Alignment, Display, Error as FormatError, Formatter, Write};
+use std::time::Duration;
+
+use exact::{self, Ticks};
+use float_duration;
+use unit::UNITS;
+use {FloatDuration, SignedDuration, TimeUnit};
+
+/// A formatting newtype for providing a
+/// [`Display`] implementation. This format is
+/// meant to be used for printing performance measurements.
+///
+/// # Behaviour
+///
+/// * `secs >= 1` => seconds with up to 3 decimal places
+///   (or minutes, hours and days if enabled with a [style])
+/// * `secs >= 0.001` => milliseconds with up to 3 decimal places
+/// * `secs >= 0.000_001` => microseconds with up to 3 decimal places
+/// * otherwise => nanoseconds
+///
+/// The unit is picked for the exact value, so the printed number is
+/// always at least 1 (unless it's in nanoseconds). If rounding would
+/// print 1000 of a unit, the next one is used instead; e.g. 999.9996µs
+/// is printed as `1ms`, but 999.5µs as `999.5µs`.
+///
+/// If a [precision] is given (e.g. `{:.1}`), the value is printed with
+/// exactly that many decimal places, padded with trailing zeros.
+/// Width, fill, alignment and the `+` flag are honored as well;
+/// like strings, durations are left-aligned by default.
+///
+/// By default the duration is formatted using abbreviated units
+/// (e.g. `1.234ms`).
+/// If the the format string is specified with the [alternate flag] `{:#}`,
+/// the duration is formatted using the full unit name instead
+/// (e.g. `1.234 milliseconds`, but `1 millisecond`).
+///
+/// Both forms can be [parsed] back into a `Duration`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::TimeFormat;
+///
+/// let dur = Duration::new(0, 461_930);
+/// let formatted = format!("{}", TimeFormat(dur));
+/// assert_eq!(formatted, "461.93µs");
+/// let alternate = format!("{:#}", TimeFormat(dur));
+/// assert_eq!(alternate, "461.93 microseconds");
+/// let singular = format!("{:#}", TimeFormat(Duration::new(1, 0)));
+/// assert_eq!(singular, "1 second");
+/// let precise = format!("{:.1}", TimeFormat(dur));
+/// assert_eq!(precise, "461.9µs");
+/// let padded = format!("{:.5}", TimeFormat(dur));
+/// assert_eq!(padded, "461.93000µs");
+/// let aligned = format!("[{:>10}]", TimeFormat(dur));
+/// assert_eq!(aligned, "[  461.93µs]");
+/// let filled = format!("[{:*^+#23.1}]", TimeFormat(dur));
+/// assert_eq!(filled, "[**+461.9 microseconds**]");
+/// ```
+///
+/// [`Display`]: https://doc.rust-lang.org/stable/std/fmt/trait.Display.html
+/// [alternate flag]: https://doc.rust-lang.org/stable/std/fmt/#sign0
+/// [precision]: https://doc.rust-lang.org/stable/std/fmt/#precision
+/// [style]: struct.TimeFormatStyle.html
+/// [parsed]: #impl-FromStr
+#[derive(Clone, Copy, Debug)]
+pub struct TimeFormat<T>(pub T);
+
+impl<T> TimeFormat<T> {
+    /// Creates a formatter which uses the given `style`
+    /// instead of the default one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use floating_duration::{TimeFormat, TimeFormatStyle, TimeUnit};
+    ///
+    /// let style = TimeFormatStyle::new().max_unit(TimeUnit::Days);
+    /// let dur = Duration::new(10_800, 0);
+    ///
+    /// assert_eq!(format!("{}", TimeFormat(dur)), "10800s");
+    /// assert_eq!(format!("{}", TimeFormat::with_style(dur, style)), "3h");
+    /// assert_eq!(format!("{:#}", TimeFormat::with_style(dur, style)), "3 hours");
+    /// ```
+    pub fn with_style(dur: T, style: TimeFormatStyle) -> StyledTimeFormat<T> {
+        StyledTimeFormat { dur, style }
+    }
+
+    /// Creates a formatter which always uses `unit`, e.g. to
+    /// make values in a report comparable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use floating_duration::{TimeFormat, TimeUnit};
+    ///
+    /// let fmt = |nanos| TimeFormat::in_unit(Duration::new(0, nanos), TimeUnit::Millis);
+    ///
+    /// assert_eq!(format!("{}", fmt(980_000)), "0.98ms");
+    /// assert_eq!(format!("{}", fmt(1_200_000)), "1.2ms");
+    /// assert_eq!(format!("{:.2}", fmt(980_000)), "0.98ms");
+    /// assert_eq!(format!("{:#.1}", fmt(1_000_000)), "1.0 milliseconds");
+    /// ```
+    pub fn in_unit(dur: T, unit: TimeUnit) -> StyledTimeFormat<T> {
+        TimeFormat::with_style(dur, TimeFormatStyle::new().unit(unit))
+    }
+}
+
+impl<T: Borrow<Duration>> Display for TimeFormat<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        TimeFormat::with_style(self.0.borrow(), TimeFormatStyle::new()).fmt(f)
+    }
+}
+
+impl Display for TimeFormat<SignedDuration> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        TimeFormat::with_style(self.0, TimeFormatStyle::new()).fmt(f)
+    }
+}
+
+impl Display for TimeFormat<FloatDuration> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        TimeFormat::with_style(self.0, TimeFormatStyle::new()).fmt(f)
+    }
+}
+
+/// Options for formatting a duration, used by
+/// [`TimeFormat::with_style`].
+///
+/// The default style produces the same output as [`TimeFormat`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::{RoundingMode, TimeFormat, TimeFormatStyle, TimeUnit};
+///
+/// let dur = Duration::new(0, 1_234_567);
+/// let style = TimeFormatStyle::new()
+///     .decimals(2)
+///     .min_unit(TimeUnit::Micros)
+///     .space(true)
+///     .strip_zeros(false)
+///     .rounding(RoundingMode::Truncate);
+///
+/// assert_eq!(format!("{}", TimeFormat::with_style(dur, style)), "1.23 ms");
+/// assert_eq!(format!("{:.4}", TimeFormat::with_style(dur, style)), "1.2345 ms");
+///
+/// let dur = Duration::new(0, 500);
+/// assert_eq!(format!("{}", TimeFormat::with_style(dur, style)), "0.50 µs");
+///
+/// let style = TimeFormatStyle::new().ascii(true);
+/// assert_eq!(format!("{}", TimeFormat::with_style(dur, style)), "500ns");
+///
+/// let dur = Duration::new(0, 2_500);
+/// assert_eq!(format!("{}", TimeFormat::with_style(dur, style)), "2.5us");
+///
+/// let style = TimeFormatStyle::new().long_names(true);
+/// assert_eq!(format!("{}", TimeFormat::with_style(dur, style)), "2.5 microseconds");
+/// ```
+///
+/// [`TimeFormat`]: struct.TimeFormat.html
+/// [`TimeFormat::with_style`]: struct.TimeFormat.html#method.with_style
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimeFormatStyle {
+    decimals: usize,
+    min_unit: TimeUnit,
+    max_unit: TimeUnit,
+    ascii: bool,
+    space: bool,
+    strip_zeros: bool,
+    rounding: RoundingMode,
+    long_names: bool,
+    significant_figures: Option<usize>,
+}
+
+impl TimeFormatStyle {
+    /// Creates the default style.
+    pub fn new() -> Self {
+        TimeFormatStyle {
+            decimals: 3,
+            min_unit: TimeUnit::Nanos,
+            max_unit: TimeUnit::Secs,
+            ascii: false,
+            space: false,
+            strip_zeros: true,
+            rounding: RoundingMode::HalfUp,
+            long_names: false,
+            significant_figures: None,
+        }
+    }
+
+    /// Sets the number of decimal places, which defaults to 3.
+    ///
+    /// A [precision] given in the format string takes precedence.
+    ///
+    /// [precision]: https://doc.rust-lang.org/stable/std/fmt/#precision
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+
+        self
+    }
+
+    /// Sets the smallest unit that is chosen automatically;
+    /// smaller values are printed as a fraction of it.
+    ///
+    /// Defaults to nanoseconds.
+    pub fn min_unit(mut self, unit: TimeUnit) -> Self {
+        self.min_unit = unit;
+
+        self
+    }
+
+    /// Sets the largest unit that is chosen automatically.
+    ///
+    /// Defaults to seconds; use `TimeUnit::Days` to scale
+    /// long durations to minutes, hours and days.
+    pub fn max_unit(mut self, unit: TimeUnit) -> Self {
+        self.max_unit = unit;
+
+        self
+    }
+
+    /// Always uses `unit`, setting both the [smallest] and
+    /// the [largest] unit to it.
+    ///
+    /// [smallest]: #method.min_unit
+    /// [largest]: #method.max_unit
+    pub fn unit(self, unit: TimeUnit) -> Self {
+        self.min_unit(unit).max_unit(unit)
+    }
+
+    /// Uses `us` instead of `µs` as the abbreviation for microseconds.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+
+        self
+    }
+
+    /// Puts a space between the number and an abbreviated unit
+    /// (e.g. `1.5 ms`). Full unit names are always separated.
+    pub fn space(mut self, space: bool) -> Self {
+        self.space = space;
+
+        self
+    }
+
+    /// Sets whether trailing zeros are removed from the decimal places,
+    /// which is the default. Otherwise, all the [decimals] are printed.
+    ///
+    /// [decimals]: #method.decimals
+    pub fn strip_zeros(mut self, strip: bool) -> Self {
+        self.strip_zeros = strip;
+
+        self
+    }
+
+    /// Sets how the value is rounded to the decimal places.
+    ///
+    /// Defaults to `RoundingMode::HalfUp`.
+    pub fn rounding(mut self, mode: RoundingMode) -> Self {
+        self.rounding = mode;
+
+        self
+    }
+
+    /// Uses the full unit names (e.g. `1.5 milliseconds`),
+    /// like the [alternate flag] does.
+    ///
+    /// [alternate flag]: https://doc.rust-lang.org/stable/std/fmt/#sign0
+    pub fn long_names(mut self, long: bool) -> Self {
+        self.long_names = long;
+
+        self
+    }
+    /// Prints the value with `figures` significant figures instead of
+    /// a fixed number of decimal places, e.g. `123.5ms`, `1.234µs`
+    /// and `12.35s` with 4 figures. Trailing zeros are kept, since
+    /// they are significant.
+    ///
+    /// If rounding carries into the next unit, that unit is used
+    /// instead (`999.96µs` is printed as `1.000ms`). A [precision]
+    /// given in the format string takes precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use floating_duration::{TimeFormat, TimeFormatStyle};
+    ///
+    /// let style = TimeFormatStyle::new().significant_figures(4);
+    /// let fmt = |secs, nanos| format!("{}", TimeFormat::with_style(Duration::new(secs, nanos), style));
+    ///
+    /// assert_eq!(fmt(0, 123_456_789), "123.5ms");
+    /// assert_eq!(fmt(0, 1_234), "1.234µs");
+    /// assert_eq!(fmt(12, 345_678_901), "12.35s");
+    /// assert_eq!(fmt(0, 999_960), "1.000ms");
+    /// ```
+    ///
+    /// [precision]: https://doc.rust-lang.org/stable/std/fmt/#precision
+    pub fn significant_figures(mut self, figures: usize) -> Self {
+        self.significant_figures = Some(cmp::max(figures, 1));
+
+        self
+    }
+}
+
+impl Default for TimeFormatStyle {
+    fn default() -> Self {
+        TimeFormatStyle::new()
+    }
+}
+
+/// How a value is rounded to the printed number of decimal places.
+///
+/// The rounding is done on the exact number of nanoseconds, so it
+/// is deterministic. It is supported by [`TimeFormatStyle`],
+/// [`CompoundFormat`], [`ClockFormat`], [`DurationTemplate`],
+/// [`Iso8601Format`], [`ExactFormat`] and [`PerIterationFormat`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::{RoundingMode, TimeFormat, TimeFormatStyle};
+///
+/// let fmt = |nanos, mode| {
+///     let style = TimeFormatStyle::new().decimals(0).rounding(mode);
+///
+///     format!("{}", TimeFormat::with_style(Duration::new(0, nanos), style))
+/// };
+///
+/// assert_eq!(fmt(2_500_000, RoundingMode::HalfUp), "3ms");
+/// assert_eq!(fmt(2_500_000, RoundingMode::HalfEven), "2ms");
+/// assert_eq!(fmt(3_500_000, RoundingMode::HalfEven), "4ms");
+/// assert_eq!(fmt(2_999_999, RoundingMode::Truncate), "2ms");
+/// assert_eq!(fmt(2_000_001, RoundingMode::Ceil), "3ms");
+/// ```
+///
+/// [`TimeFormatStyle`]: struct.TimeFormatStyle.html#method.rounding
+/// [`CompoundFormat`]: struct.CompoundFormat.html#method.rounding
+/// [`ClockFormat`]: struct.ClockFormat.html#method.rounding
+/// [`DurationTemplate`]: struct.DurationTemplate.html#method.rounding
+/// [`Iso8601Format`]: struct.Iso8601Format.html#method.rounding
+/// [`ExactFormat`]: struct.ExactFormat.html#method.rounding
+/// [`PerIterationFormat`]: struct.PerIterationFormat.html#method.rounding
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to the nearest value, ties away from zero.
+    HalfUp,
+    /// Round to the nearest value, ties to the even one.
+    HalfEven,
+    /// Round towards zero, dropping the remaining digits.
+    Truncate,
+    /// Round towards negative infinity; the same as `Truncate`
+    /// for non-negative durations.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+}
+
+impl Default for RoundingMode {
+    fn default() -> Self {
+        RoundingMode::HalfUp
+    }
+}
+
+/// A [`TimeFormat`] with a custom [`TimeFormatStyle`],
+/// created by [`TimeFormat::with_style`].
+///
+/// [`TimeFormat`]: struct.TimeFormat.html
+/// [`TimeFormatStyle`]: struct.TimeFormatStyle.html
+/// [`TimeFormat::with_style`]: struct.TimeFormat.html#method.with_style
+#[derive(Clone, Copy, Debug)]
+pub struct StyledTimeFormat<T> {
+    dur: T,
+    style: TimeFormatStyle,
+}
+
+impl<T: Borrow<Duration>> Display for StyledTimeFormat<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let value = Ticks::from_nanos(exact::total_nanos(self.dur.borrow()));
+        let mut buf = String::new();
+        write_styled(&mut buf, value, &self.style, f.alternate(), f.precision())?;
+
+        pad(f, &buf)
+    }
+}
+
+impl Display for StyledTimeFormat<SignedDuration> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let value = Ticks::from_nanos(exact::total_nanos(&self.dur.abs()));
+        let mut buf = String::new();
+        write_signed(
+            &mut buf,
+            self.dur.is_negative(),
+            value,
+            &self.style,
+            f.alternate(),
+            f.precision(),
+        )?;
+
+        pad(f, &buf)
+    }
+}
+
+impl Display for StyledTimeFormat<FloatDuration> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let secs = self.dur.0;
+        let mut buf = String::new();
+        match float_duration::exact_abs(secs) {
+            Some(value) => write_signed(
+                &mut buf,
+                secs < 0.0,
+                value,
+                &self.style,
+                f.alternate(),
+                f.precision(),
+            )?,
+            None if secs.is_finite() => {
+                let number = format!("{:e}", secs);
+                if f.alternate() || self.style.long_names {
+                    write!(buf, "{} {}", number, TimeUnit::Secs.name_for(&number))?
+                } else {
+                    write!(buf, "{}{}", number, TimeUnit::Secs.abbreviation())?
+                }
+            }
+            None => write!(buf, "{}", secs)?,
+        }
+
+        pad(f, &buf)
+    }
+}
+
+/// Like `write_styled`, but with a leading `-` if `negative`
+/// and the printed value isn't zero.
+fn write_signed<W: Write>(
+    w: &mut W,
+    negative: bool,
+    value: Ticks,
+    style: &TimeFormatStyle,
+    alternate: bool,
+    precision: Option<usize>,
+) -> Result<(), FormatError> {
+    let mut style = *style;
+    if negative {
+        // Rounding is done on the magnitude, which flips its direction.
+        style.rounding = match style.rounding {
+            RoundingMode::Floor => RoundingMode::Ceil,
+            RoundingMode::Ceil => RoundingMode::Floor,
+            mode => mode,
+        };
+    }
+    let mut buf = String::new();
+    write_styled(&mut buf, value, &style, alternate, precision)?;
+
+    // Don't print a negative zero.
+    if negative && buf.contains(|c| c >= '1' && c <= '9') {
+        w.write_char('-')?;
+    }
+
+    w.write_str(&buf)
+}
+
+/// Writes `value` as specified by `style` and the format flags.
+fn write_styled<W: Write>(
+    w: &mut W,
+    value: Ticks,
+    style: &TimeFormatStyle,
+    alternate: bool,
+    precision: Option<usize>,
+) -> Result<(), FormatError> {
+    let (unit, decimals, padding) = match (precision, style.significant_figures) {
+        (None, Some(figures)) => {
+            let (unit, decimals) = significant(value, style, figures);
+
+            (unit, decimals, Some(cmp::max(decimals, 0) as usize))
+        }
+        (None, None) if style.strip_zeros => {
+            (unit_for(value, style, None), style.decimals as isize, None)
+        }
+        _ => {
+            let decimals = precision.unwrap_or(style.decimals);
+
+            (
+                unit_for(value, style, Some(decimals)),
+                decimals as isize,
+                Some(decimals),
+            )
+        }
+    };
+    let mut number = String::new();
+    if decimals >= 0 {
+        exact::write_quotient(
+            &mut number,
+            value.ticks,
+            value.per_unit(unit),
+            decimals as usize,
+            style.rounding,
+            padding,
+        )?;
+    } else {
+        let (scaled, places) = round_places(value, unit, decimals, style.rounding);
+        exact::write_decimal(&mut number, scaled, places, padding)?;
+    }
+
+    if alternate || style.long_names {
+        write!(w, "{} {}", number, unit.name_for(&number))
+    } else {
+        let abbreviation = match unit {
+            TimeUnit::Micros if style.ascii => "us",
+            _ => unit.abbreviation(),
+        };
+        let space = if style.space { " " } else { "" };
+
+        write!(w, "{}{}{}", number, space, abbreviation)
+    }
+}
+
+/// Picks the largest unit allowed by `style` in which `value` is at
+/// least one, or the next one if rounding to `precision` (or the style's
+/// decimals) places carries over into it.
+pub fn unit_for(value: Ticks, style: &TimeFormatStyle, precision: Option<usize>) -> TimeUnit {
+    let decimals = precision.unwrap_or(style.decimals);
+
+    let unit = UNITS
+        .iter()
+        .rev()
+        .cloned()
+        .filter(|&unit| unit >= style.min_unit && unit <= style.max_unit)
+        .find(|&unit| value.ticks >= value.per_unit(unit))
+        .unwrap_or(style.min_unit);
+
+    match next_unit(unit, style) {
+        Some(next) => {
+            let per_unit = value.per_unit(unit);
+            let (scaled, places) =
+                exact::round_ticks(value.ticks, per_unit, decimals, style.rounding);
+            // The units are whole multiples of each other.
+            if scaled / 10u128.pow(places as u32) >= value.per_unit(next) / per_unit {
+                return next;
+            }
+
+            unit
+        }
+        None => unit,
+    }
+}
+
+/// The unit after `unit`, if `style` allows it.
+fn next_unit(unit: TimeUnit, style: &TimeFormatStyle) -> Option<TimeUnit> {
+    UNITS
+        .iter()
+        .cloned()
+        .find(|&next| next > unit)
+        .filter(|&next| next <= style.max_unit)
+}
+
+/// Picks the unit and the number of decimal places for printing
+/// `value` with `figures` significant figures; negative decimals
+/// round to tens, hundreds and so on.
+fn significant(value: Ticks, style: &TimeFormatStyle, figures: usize) -> (TimeUnit, isize) {
+    let figures = figures as isize;
+    let digits = |n: u128| n.to_string().len() as isize;
+
+    let unit = UNITS
+        .iter()
+        .rev()
+        .cloned()
+        .filter(|&unit| unit >= style.min_unit && unit <= style.max_unit)
+        .find(|&unit| value.ticks >= value.per_unit(unit))
+        .unwrap_or(style.min_unit);
+    let int = value.ticks / value.per_unit(unit);
+
+    if int == 0 {
+        // The value is below one of the smallest unit.
+        if value.ticks == 0 {
+            return (unit, 0);
+        }
+        let mut leading_zeros = 0;
+        while value.ticks * 10u128.pow(leading_zeros as u32 + 1) < value.per_unit(unit) {
+            leading_zeros += 1;
+        }
+        let decimals = leading_zeros + figures;
+        let (scaled, _) = round_places(value, unit, decimals, style.rounding);
+        if digits(scaled) > figures {
+            return (unit, decimals - 1);
+        }
+
+        return (unit, decimals);
+    }
+
+    let decimals = figures - digits(int);
+    let (scaled, places) = round_places(value, unit, decimals, style.rounding);
+    let rounded = scaled / 10u128.pow(places as u32);
+
+    match next_unit(unit, style) {
+        Some(next) if rounded * value.per_unit(unit) >= value.per_unit(next) => (next, figures - 1),
+        _ if digits(rounded) > digits(int) => (unit, decimals - 1),
+        _ => (unit, decimals),
+    }
+}
+
+/// Like `exact::round_ticks`, but negative `decimals`
+/// round to tens, hundreds and so on.
+fn round_places(
+    value: Ticks,
+    unit: TimeUnit,
+    decimals: isize,
+    mode: RoundingMode,
+) -> (u128, usize) {
+    if decimals >= 0 {
+        return exact::round_ticks(value.ticks, value.per_unit(unit), decimals as usize, mode);
+    }
+
+    let pow = 10u128.pow(-decimals as u32);
+
+    (
+        exact::divide(value.ticks, value.per_unit(unit) * pow, mode) * pow,
+        0,
+    )
+}
+
+/// The number of decimal places `style` prints for `value` in `unit`
+/// if no precision is given.
+pub fn decimals_for(value: Ticks, unit: TimeUnit, style: &TimeFormatStyle) -> usize {
+    let (mut scaled, mut places) = exact::round_ticks(
+        value.ticks,
+        value.per_unit(unit),
+        style.decimals,
+        style.rounding,
+    );
+    if !style.strip_zeros {
+        return style.decimals;
+    }
+
+    while places > 0 && scaled % 10 == 0 {
+        scaled /= 10;
+        places -= 1;
+    }
+
+    places
+}
+
+/// Writes `s` to `f`, honoring the sign, width, fill and alignment flags.
+///
+/// Unlike `Formatter::pad`, this doesn't truncate `s` to the precision,
+/// which is used for the number of decimal places instead. No `+` is
+/// added if `s` already starts with a `-`.
+pub fn pad(f: &mut Formatter, s: &str) -> Result<(), FormatError> {
+    pad_aligned(f, s, Alignment::Left)
+}
+
+/// Like `pad`, but with `default` as the alignment if none is given.
+pub fn pad_aligned(f: &mut Formatter, s: &str, default: Alignment) -> Result<(), FormatError> {
+    let sign = if f.sign_plus() && !s.starts_with('-') {
+        "+"
+    } else {
+        ""
+    };
+    let len = sign.len() + s.chars().count();
+
+    let padding = match f.width() {
+        Some(width) if width > len => width - len,
+        _ => {
+            f.write_str(sign)?;
+            return f.write_str(s);
+        }
+    };
+    let (before, after) = match f.align().unwrap_or(default) {
+        Alignment::Left => (0, padding),
+        Alignment::Right => (padding, 0),
+        Alignment::Center => (padding / 2, (padding + 1) / 2),
+    };
+
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(sign)?;
+    f.write_str(s)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+
+    Ok(())
+}
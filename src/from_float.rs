@@ -0,0 +1,148 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::error::Error;
+use std::fmt::{Display, Error as FormatError, Formatter};
+use std::time::Duration;
+
+use exact;
+
+/// Trait for building a `Duration` from fractional numbers.
+///
+/// This is the inverse of [`TimeAsFloat`]. The value is rounded
+/// to the nearest nanosecond (ties to even); invalid inputs result
+/// in a [`FromFloatError`] instead of a panic.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::DurationFromFloat;
+///
+/// let dur = Duration::from_fractional_secs(1.5).unwrap();
+/// assert_eq!(dur, Duration::new(1, 500_000_000));
+///
+/// let dur = Duration::from_fractional_millis(0.25).unwrap();
+/// assert_eq!(dur, Duration::new(0, 250_000));
+///
+/// assert!(Duration::from_fractional_secs(-1.0).is_err());
+/// ```
+///
+/// [`TimeAsFloat`]: trait.TimeAsFloat.html
+/// [`FromFloatError`]: enum.FromFloatError.html
+pub trait DurationFromFloat: Sized {
+    /// Creates a duration from seconds.
+    fn from_fractional_secs(secs: f64) -> Result<Self, FromFloatError>;
+    /// Creates a duration from milliseconds.
+    fn from_fractional_millis(millis: f64) -> Result<Self, FromFloatError>;
+    /// Creates a duration from microseconds.
+    fn from_fractional_micros(micros: f64) -> Result<Self, FromFloatError>;
+    /// Creates a duration from nanoseconds.
+    fn from_fractional_nanos(nanos: f64) -> Result<Self, FromFloatError>;
+}
+
+impl DurationFromFloat for Duration {
+    fn from_fractional_secs(secs: f64) -> Result<Self, FromFloatError> {
+        from_float(secs, 1_000_000_000)
+    }
+
+    fn from_fractional_millis(millis: f64) -> Result<Self, FromFloatError> {
+        from_float(millis, 1_000_000)
+    }
+
+    fn from_fractional_micros(micros: f64) -> Result<Self, FromFloatError> {
+        from_float(micros, 1_000)
+    }
+
+    fn from_fractional_nanos(nanos: f64) -> Result<Self, FromFloatError> {
+        from_float(nanos, 1)
+    }
+}
+
+/// The error returned when a float cannot be converted to a `Duration`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FromFloatError {
+    /// The value was NaN.
+    NotANumber,
+    /// The value was positive or negative infinity.
+    Infinite,
+    /// The value was negative.
+    Negative,
+    /// The value is too large to be represented by a `Duration`.
+    OutOfRange,
+}
+
+impl Display for FromFloatError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let msg = match *self {
+            FromFloatError::NotANumber => "value is NaN",
+            FromFloatError::Infinite => "value is infinite",
+            FromFloatError::Negative => "value is negative",
+            FromFloatError::OutOfRange => "value is too large for a duration",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+impl Error for FromFloatError {}
+
+/// Converts `value * unit_nanos` nanoseconds to a `Duration`,
+/// rounding the exact product to the nearest nanosecond.
+fn from_float(value: f64, unit_nanos: u64) -> Result<Duration, FromFloatError> {
+    if value.is_nan() {
+        return Err(FromFloatError::NotANumber);
+    }
+    if value.is_infinite() {
+        return Err(FromFloatError::Infinite);
+    }
+    if value < 0.0 {
+        return Err(FromFloatError::Negative);
+    }
+
+    // `value == mantissa * 2^exp`, exactly.
+    let bits = value.to_bits();
+    let biased_exp = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exp) = if biased_exp == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased_exp - 1075)
+    };
+
+    let product = mantissa as u128 * unit_nanos as u128;
+    if product == 0 {
+        return Ok(Duration::new(0, 0));
+    }
+
+    let nanos = if exp >= 0 {
+        if exp as u32 >= product.leading_zeros() {
+            return Err(FromFloatError::OutOfRange);
+        }
+
+        product << exp
+    } else {
+        let shift = -exp as u32;
+        if shift >= 128 {
+            // `product < 2^83`, so the value is below half a nanosecond.
+            0
+        } else {
+            let quotient = product >> shift;
+            let remainder = product & ((1 << shift) - 1);
+            let half = 1 << (shift - 1);
+
+            if remainder > half || (remainder == half && quotient & 1 == 1) {
+                quotient + 1
+            } else {
+                quotient
+            }
+        }
+    };
+
+    exact::duration_from_nanos(nanos).ok_or(FromFloatError::OutOfRange)
+}
@@ -0,0 +1,203 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Borrow;
+use std::fmt::{Alignment, Display, Error as FormatError, Formatter};
+use std::time::Duration;
+
+use exact::{self, Ticks};
+use format::{decimals_for, pad_aligned, unit_for};
+use {TimeFormat, TimeFormatStyle, TimeUnit};
+
+/// Formats a slice of durations in one shared unit and
+/// number of decimal places, see [`TimeFormatGroup`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::format_all;
+///
+/// let durations = [
+///     Duration::new(0, 980_000),
+///     Duration::new(0, 1_250_000),
+///     Duration::new(0, 12_000_000),
+/// ];
+///
+/// let formatted: Vec<_> = format_all(&durations)
+///     .iter()
+///     .map(|f| format!("{:7}", f))
+///     .collect();
+/// assert_eq!(formatted, [" 0.98ms", " 1.25ms", "12.00ms"]);
+/// ```
+///
+/// [`TimeFormatGroup`]: struct.TimeFormatGroup.html
+pub fn format_all<T: Borrow<Duration>>(durations: &[T]) -> Vec<GroupedTimeFormat<&Duration>> {
+    let group = TimeFormatGroup::new(durations.iter().map(Borrow::borrow));
+
+    durations.iter().map(|d| group.format(d.borrow())).collect()
+}
+
+/// Which value of a group the shared unit is chosen for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GroupBasis {
+    /// The median (the lower one for an even number of values).
+    Median,
+    /// The largest value.
+    Max,
+}
+
+/// A unit and number of decimal places shared by a group of
+/// durations, e.g. for the rows of a table.
+///
+/// The unit is the one [`TimeFormat`] would pick for the median (or the
+/// largest) duration. All values are then printed with the same number
+/// of decimal places, the most any value needs (up to the style's
+/// decimals, or the [precision] if one is given).
+///
+/// The formatted values are right-aligned by default, so the decimal
+/// points line up when a width is given. Spelled-out singular unit names
+/// are then padded to the length of the plural, e.g. `1 second ` to
+/// line up with `2 seconds`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::{GroupBasis, TimeFormatGroup, TimeFormatStyle, TimeUnit};
+///
+/// let durations = vec![
+///     Duration::new(0, 980_000),
+///     Duration::new(1, 500_000_000),
+///     Duration::new(0, 2_000_000),
+/// ];
+///
+/// let group = TimeFormatGroup::new(&durations);
+/// assert_eq!(group.unit(), TimeUnit::Millis);
+/// assert_eq!(format!("[{:>9}]", group.format(durations[1])), "[1500.00ms]");
+/// assert_eq!(format!("[{:>9}]", group.format(durations[2])), "[   2.00ms]");
+///
+/// let style = TimeFormatStyle::new();
+/// let group = TimeFormatGroup::with_style(&durations, style, GroupBasis::Max);
+/// assert_eq!(group.unit(), TimeUnit::Secs);
+/// assert_eq!(format!("{}", group.format(durations[0])), "0.001s");
+/// assert_eq!(format!("{:#.1}", group.format(durations[1])), "1.5 seconds");
+/// ```
+///
+/// [`TimeFormat`]: struct.TimeFormat.html
+/// [precision]: https://doc.rust-lang.org/stable/std/fmt/#precision
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TimeFormatGroup {
+    unit: TimeUnit,
+    decimals: usize,
+    style: TimeFormatStyle,
+}
+
+impl TimeFormatGroup {
+    /// Creates a group with the default style,
+    /// choosing the unit for the median.
+    pub fn new<I>(durations: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Duration>,
+    {
+        TimeFormatGroup::with_style(durations, TimeFormatStyle::new(), GroupBasis::Median)
+    }
+
+    /// Creates a group with a custom style, choosing
+    /// the unit for the value specified by `basis`.
+    ///
+    /// The style's [minimum] and [maximum] units are respected.
+    ///
+    /// [minimum]: struct.TimeFormatStyle.html#method.min_unit
+    /// [maximum]: struct.TimeFormatStyle.html#method.max_unit
+    pub fn with_style<I>(durations: I, style: TimeFormatStyle, basis: GroupBasis) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Duration>,
+    {
+        let mut nanos: Vec<u128> = durations
+            .into_iter()
+            .map(|d| exact::total_nanos(d.borrow()))
+            .collect();
+        nanos.sort();
+
+        let representative = match basis {
+            GroupBasis::Median if !nanos.is_empty() => nanos[(nanos.len() - 1) / 2],
+            _ => nanos.last().cloned().unwrap_or(0),
+        };
+        let unit = unit_for(Ticks::from_nanos(representative), &style, None);
+        let decimals = nanos
+            .iter()
+            .map(|&n| decimals_for(Ticks::from_nanos(n), unit, &style))
+            .max()
+            .unwrap_or(0);
+
+        TimeFormatGroup {
+            unit,
+            decimals,
+            style,
+        }
+    }
+
+    /// Returns the shared unit.
+    pub fn unit(&self) -> TimeUnit {
+        self.unit
+    }
+
+    /// Returns the shared number of decimal places.
+    pub fn decimals(&self) -> usize {
+        self.decimals
+    }
+
+    /// Returns a value which formats `dur` in the shared unit.
+    pub fn format<T: Borrow<Duration>>(&self, dur: T) -> GroupedTimeFormat<T> {
+        let style = self
+            .style
+            .unit(self.unit)
+            .decimals(self.decimals)
+            .strip_zeros(false);
+
+        GroupedTimeFormat {
+            dur,
+            unit: self.unit,
+            style,
+        }
+    }
+}
+
+/// A duration formatted as part of a [`TimeFormatGroup`].
+///
+/// [`TimeFormatGroup`]: struct.TimeFormatGroup.html
+#[derive(Clone, Copy, Debug)]
+pub struct GroupedTimeFormat<T: Borrow<Duration>> {
+    dur: T,
+    unit: TimeUnit,
+    style: TimeFormatStyle,
+}
+
+impl<T: Borrow<Duration>> Display for GroupedTimeFormat<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let mut style = self.style;
+        if let Some(precision) = f.precision() {
+            style = style.decimals(precision);
+        }
+        if f.alternate() {
+            style = style.long_names(true);
+        }
+        let mut buf = TimeFormat::with_style(self.dur.borrow(), style).to_string();
+        if f.width().is_some() && buf.ends_with(self.unit.singular_name()) {
+            let extra = self.unit.plural_name().len() - self.unit.singular_name().len();
+            for _ in 0..extra {
+                buf.push(f.fill());
+            }
+        }
+
+        pad_aligned(f, &buf, Alignment::Right)
+    }
+}
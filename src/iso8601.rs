@@ -0,0 +1,280 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Borrow;
+use std::fmt::{Display, Error as FormatError, Formatter, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use exact;
+use format::pad;
+use parse::{self, error_at, Cursor, ExactNanos, ParseError, ParseErrorKind};
+use {RoundingMode, TimeUnit};
+
+/// A formatting newtype which prints a duration
+/// in the [ISO 8601] format, e.g. `PT1H2M3.456S`.
+///
+/// # Behaviour
+///
+/// The duration is split into hours, minutes and seconds; components
+/// which are zero are left out, unless the whole duration is zero
+/// (`PT0S`). Days are not used since they are nominal in ISO 8601,
+/// so e.g. 26 hours are printed as `PT26H`.
+///
+/// Fractional seconds are printed with as many digits as needed
+/// (up to nanoseconds), or with exactly as many as the [precision]
+/// specifies; they are rounded half up unless a different
+/// [rounding mode] is set. Width, fill, alignment and the `+` flag
+/// are honored like for [`TimeFormat`].
+///
+/// The output can be parsed back with [`parse_iso8601`],
+/// or with the `FromStr` implementation of this type.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::Iso8601Format;
+///
+/// let dur = Duration::new(3_723, 456_000_000);
+/// assert_eq!(format!("{}", Iso8601Format(dur)), "PT1H2M3.456S");
+/// assert_eq!(format!("{:.1}", Iso8601Format(dur)), "PT1H2M3.5S");
+/// assert_eq!(format!("{}", Iso8601Format(Duration::new(0, 0))), "PT0S");
+///
+/// let Iso8601Format(parsed) = "PT1H2M3.456S".parse().unwrap();
+/// assert_eq!(parsed, dur);
+/// ```
+///
+/// [ISO 8601]: https://en.wikipedia.org/wiki/ISO_8601#Durations
+/// [precision]: https://doc.rust-lang.org/stable/std/fmt/#precision
+/// [rounding mode]: #method.rounding
+/// [`TimeFormat`]: struct.TimeFormat.html
+/// [`parse_iso8601`]: fn.parse_iso8601.html
+#[derive(Clone, Copy, Debug)]
+pub struct Iso8601Format<T: Borrow<Duration>>(pub T);
+
+impl<T: Borrow<Duration>> Iso8601Format<T> {
+    /// Sets how the seconds are rounded to the printed number of
+    /// decimal places; the default is [`RoundingMode::HalfUp`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use floating_duration::{Iso8601Format, RoundingMode};
+    ///
+    /// let dur = Duration::new(59, 999_600_000);
+    /// assert_eq!(format!("{:.3}", Iso8601Format(dur)), "PT1M");
+    /// assert_eq!(format!("{:.3}", Iso8601Format(dur).rounding(RoundingMode::Truncate)), "PT59.999S");
+    /// ```
+    ///
+    /// [`RoundingMode::HalfUp`]: enum.RoundingMode.html#variant.HalfUp
+    pub fn rounding(self, mode: RoundingMode) -> RoundedIso8601Format<T> {
+        RoundedIso8601Format { dur: self.0, mode }
+    }
+}
+
+impl<T: Borrow<Duration>> Display for Iso8601Format<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        RoundedIso8601Format {
+            dur: self.0.borrow(),
+            mode: RoundingMode::HalfUp,
+        }
+        .fmt(f)
+    }
+}
+
+/// An [`Iso8601Format`] with a custom rounding mode, created by
+/// [`Iso8601Format::rounding`].
+///
+/// [`Iso8601Format`]: struct.Iso8601Format.html
+/// [`Iso8601Format::rounding`]: struct.Iso8601Format.html#method.rounding
+#[derive(Clone, Copy, Debug)]
+pub struct RoundedIso8601Format<T> {
+    dur: T,
+    mode: RoundingMode,
+}
+
+impl<T: Borrow<Duration>> RoundedIso8601Format<T> {
+    fn write_unpadded<W: Write>(
+        &self,
+        w: &mut W,
+        precision: Option<usize>,
+    ) -> Result<(), FormatError> {
+        let nanos = exact::total_nanos(self.dur.borrow());
+        let (scaled, places) = exact::round_decimal(
+            nanos,
+            TimeUnit::Secs.nanos(),
+            precision.unwrap_or(9),
+            self.mode,
+        );
+        let pow = 10u128.pow(places as u32);
+        let secs = scaled / pow;
+        let fraction = scaled % pow;
+
+        let hours = secs / 3_600;
+        let minutes = secs / 60 % 60;
+        let secs = secs % 60;
+
+        w.write_str("PT")?;
+        if hours > 0 {
+            write!(w, "{}H", hours)?;
+        }
+        if minutes > 0 {
+            write!(w, "{}M", minutes)?;
+        }
+        if secs > 0 || fraction > 0 || scaled == 0 {
+            write!(w, "{}", secs)?;
+            exact::write_fraction(w, fraction, places, precision)?;
+            w.write_char('S')?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Borrow<Duration>> Display for RoundedIso8601Format<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let mut buf = String::new();
+        self.write_unpadded(&mut buf, f.precision())?;
+
+        pad(f, &buf)
+    }
+}
+
+/// Parses an ISO 8601 duration in [strict mode].
+///
+/// [strict mode]: enum.Iso8601Mode.html#variant.Strict
+impl FromStr for Iso8601Format<Duration> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        parse_iso8601(s, Iso8601Mode::Strict).map(Iso8601Format)
+    }
+}
+
+/// How [`parse_iso8601`] treats calendar units.
+///
+/// [`parse_iso8601`]: fn.parse_iso8601.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Iso8601Mode {
+    /// Years (`Y`) and months (`M` before the `T`) are rejected,
+    /// since they don't have a fixed length.
+    Strict,
+    /// Years are taken as 365 days and months as 30 days.
+    Lenient,
+}
+
+/// Parses an [ISO 8601] duration such as `PT1H2M3.456S`, `P2W` or `P1DT12H`.
+///
+/// Every component may have a fraction, using either `.` or `,` as
+/// the decimal separator. Weeks are 7 days and days are 24 hours;
+/// years and months are handled according to `mode`. The result is
+/// rounded to the nearest nanosecond.
+///
+/// A leading `+` sign is accepted, `-` only for zero durations.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::{parse_iso8601, Iso8601Mode, ParseErrorKind};
+///
+/// let dur = parse_iso8601("PT1H2M3.456S", Iso8601Mode::Strict).unwrap();
+/// assert_eq!(dur, Duration::new(3_723, 456_000_000));
+///
+/// let dur = parse_iso8601("P2W", Iso8601Mode::Strict).unwrap();
+/// assert_eq!(dur, Duration::new(14 * 86_400, 0));
+///
+/// let err = parse_iso8601("P1M", Iso8601Mode::Strict).unwrap_err();
+/// assert_eq!(err.kind(), ParseErrorKind::CalendarUnit);
+///
+/// let dur = parse_iso8601("P1M", Iso8601Mode::Lenient).unwrap();
+/// assert_eq!(dur, Duration::new(30 * 86_400, 0));
+/// ```
+///
+/// [ISO 8601]: https://en.wikipedia.org/wiki/ISO_8601#Durations
+pub fn parse_iso8601(s: &str, mode: Iso8601Mode) -> Result<Duration, ParseError> {
+    let (sign, nanos) = parse_signed(s, mode)?;
+
+    parse::unsigned(sign, nanos)
+}
+
+const DAY: u64 = 86_400_000_000_000;
+
+/// The designators before the `T`, in order, with their length
+/// and whether they are calendar units.
+const DATE: [(char, u64, bool); 4] = [
+    ('Y', 365 * DAY, true),
+    ('M', 30 * DAY, true),
+    ('W', 7 * DAY, false),
+    ('D', DAY, false),
+];
+
+/// The designators after the `T`, in order.
+const TIME: [(char, u64, bool); 3] = [
+    ('H', 3_600_000_000_000, false),
+    ('M', 60_000_000_000, false),
+    ('S', 1_000_000_000, false),
+];
+
+/// Parses the input of `parse_iso8601`, returning the offset of the minus
+/// sign if there is one, and the total number of nanoseconds (which fits
+/// into a `Duration`).
+fn parse_signed(s: &str, mode: Iso8601Mode) -> Result<(Option<usize>, u128), ParseError> {
+    let mut cursor = Cursor::new(s);
+    if cursor.is_empty() {
+        return Err(cursor.error(ParseErrorKind::Empty));
+    }
+
+    let sign = cursor.sign();
+    if !cursor.eat('P') {
+        return Err(cursor.error(ParseErrorKind::UnexpectedCharacter));
+    }
+
+    let mut in_time = false;
+    let mut designators = &DATE[..];
+    let mut components = 0;
+    let mut total = ExactNanos::default();
+    while !cursor.is_empty() {
+        if !in_time && cursor.eat('T') {
+            in_time = true;
+            designators = &TIME[..];
+            components = 0;
+            continue;
+        }
+
+        let number = cursor.number_with(&['.', ','])?;
+        let offset = cursor.offset();
+        let designator = cursor
+            .bump()
+            .ok_or_else(|| cursor.error(ParseErrorKind::MissingUnit))?;
+        let index = designators
+            .iter()
+            .position(|&(c, _, _)| c == designator)
+            .ok_or_else(|| error_at(ParseErrorKind::UnexpectedCharacter, offset))?;
+        let (_, nanos, calendar) = designators[index];
+        if calendar && mode == Iso8601Mode::Strict {
+            return Err(error_at(ParseErrorKind::CalendarUnit, offset));
+        }
+
+        total = number
+            .to_exact(nanos)?
+            .checked_add(total)
+            .and_then(|total| exact::duration_from_nanos(total.round()).map(|_| total))
+            .ok_or_else(|| error_at(ParseErrorKind::OutOfRange, number.offset()))?;
+        designators = &designators[index + 1..];
+        components += 1;
+    }
+
+    if components == 0 {
+        return Err(cursor.error(ParseErrorKind::InvalidNumber));
+    }
+
+    Ok((sign, total.round()))
+}
@@ -8,7 +8,8 @@
 
 //! A small crate which allows combining
 //! a [`Duration`]'s seconds and nanoseconds
-//! into [seconds], [milliseconds] and [microseconds].
+//! into [seconds], [milliseconds] and [microseconds]
+//! and [back again].
 //! Additionally, it allows [easy formatting] of a
 //! `Duration` for performance measurements.
 //!
@@ -25,6 +26,32 @@
 //! let secs = duration.as_fractional_secs(); // 4.12..
 //! let millis = duration.as_fractional_millis(); // 4_123.45..
 //! let micros = duration.as_fractional_micros(); // 4_123_456.78..
+//! let nanos = duration.as_fractional_nanos(); // 4_123_456_789.0
+//!
+//! let secs_f32 = duration.as_fractional_secs_f32(); // 4.12..
+//! ```
+//!
+//! Larger units and a generic [`as_fractional`] are available as well:
+//!
+//! ```
+//! use std::time::Duration;
+//! use floating_duration::{TimeAsFloat, TimeUnit};
+//!
+//! let duration = Duration::new(9_000, 0);
+//!
+//! assert_eq!(duration.as_fractional_hours(), 2.5);
+//! assert_eq!(duration.as_fractional(TimeUnit::Minutes), 150.0);
+//! ```
+//!
+//! ## Conversion from fractional
+//!
+//! ```
+//! use std::time::Duration;
+//! use floating_duration::DurationFromFloat;
+//!
+//! let duration = Duration::from_fractional_secs(4.123_456_789).unwrap();
+//!
+//! assert_eq!(duration, Duration::new(4, 123_456_789));
 //! ```
 //!
 //! ## Automatic formatting
@@ -46,19 +73,122 @@
 //!
 //! Output: `Needed 12.841µs`
 //!
+//! Longer durations can be split into [multiple units]:
+//!
+//! ```
+//! use std::time::Duration;
+//! use floating_duration::CompoundFormat;
+//!
+//! let duration = Duration::new(3_723, 456_000_000);
+//!
+//! assert_eq!(format!("{}", CompoundFormat::new(duration)), "1h 02m 3.456s");
+//! ```
+//!
+//! The time of a [single iteration] of a benchmark can
+//! go below a nanosecond:
+//!
+//! ```
+//! use std::time::Duration;
+//! use floating_duration::TimeFormat;
+//!
+//! let total = Duration::new(0, 350_000_000);
+//!
+//! assert_eq!(format!("{}", TimeFormat::per_iteration(total, 1_000_000_000)), "350ps");
+//! ```
+//!
+//! ## Other formats
+//!
+//! * [`ExactFormat`]: all nine decimal places of the seconds, like `4.123456789s`
+//! * [`Iso8601Format`]: ISO 8601 durations like `PT1H2M3.456S`
+//! * [`ClockFormat`]: stopwatch style like `01:02:03.456`
+//! * [`DurationTemplate`]: custom layouts like `%H:%M:%S.%3f`
+//! * [`TimeFormatGroup`]: one shared unit for a column of durations
+//! * [`ShortestFormat`]: the shortest lossless form, like `1.5s`
+//!
+//! ## Parsing
+//!
+//! ```
+//! use std::time::Duration;
+//! use floating_duration::parse_duration;
+//!
+//! assert_eq!(parse_duration("1m 30.5s"), Ok(Duration::new(90, 500_000_000)));
+//! ```
+//!
+//! ## Negative durations
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//! use floating_duration::{SignedDurationSince, TimeAsFloat};
+//!
+//! let start = Instant::now();
+//! let deadline = start + Duration::new(0, 1_500_000);
+//! let slack = start.signed_duration_since(deadline);
+//!
+//! assert_eq!(slack.as_fractional_millis(), -1.5);
+//! assert_eq!(format!("{}", slack), "-1.5ms");
+//! ```
+//!
+//! For results of averaging or scaling, [`FloatDuration`] stores
+//! `f64` seconds and prints values below a nanosecond, like `0.35ns`.
+//!
 //! [`Duration`]: https://doc.rust-lang.org/stable/std/time/struct.Duration.html
 //! [seconds]: trait.TimeAsFloat.html#tymethod.as_fractional_secs
 //! [milliseconds]: trait.TimeAsFloat.html#tymethod.as_fractional_millis
 //! [microseconds]: trait.TimeAsFloat.html#tymethod.as_fractional_micros
+//! [`as_fractional`]: trait.TimeAsFloat.html#method.as_fractional
+//! [back again]: trait.DurationFromFloat.html
 //!
 //! [easy formatting]: struct.TimeFormat.html
+//! [multiple units]: struct.CompoundFormat.html
+//! [single iteration]: struct.TimeFormat.html#method.per_iteration
+//! [`ExactFormat`]: struct.ExactFormat.html
+//! [`Iso8601Format`]: struct.Iso8601Format.html
+//! [`ClockFormat`]: struct.ClockFormat.html
+//! [`DurationTemplate`]: struct.DurationTemplate.html
+//! [`TimeFormatGroup`]: struct.TimeFormatGroup.html
+//! [`ShortestFormat`]: struct.ShortestFormat.html
+//! [`FloatDuration`]: struct.FloatDuration.html
+
+pub use clock::ClockFormat;
+pub use compound::CompoundFormat;
+pub use exact_format::{ExactFormat, RoundedExactFormat};
+pub use float_duration::FloatDuration;
+pub use format::{RoundingMode, StyledTimeFormat, TimeFormat, TimeFormatStyle};
+pub use from_float::{DurationFromFloat, FromFloatError};
+pub use group::{format_all, GroupBasis, GroupedTimeFormat, TimeFormatGroup};
+pub use iso8601::{parse_iso8601, Iso8601Format, Iso8601Mode, RoundedIso8601Format};
+pub use parse::{parse_duration, ParseError, ParseErrorKind};
+pub use per_iteration::PerIterationFormat;
+pub use shortest::ShortestFormat;
+pub use signed::{SignedDuration, SignedDurationSince};
+pub use template::{DurationTemplate, TemplateError, TemplateErrorKind, TemplateFormat};
+pub use unit::TimeUnit;
 
 use std::borrow::Borrow;
-use std::fmt::{Display, Error as FormatError, Formatter};
 use std::time::Duration;
 
+mod clock;
+mod compound;
+mod exact;
+mod exact_format;
+mod float_duration;
+mod format;
+mod from_float;
+mod group;
+mod iso8601;
+mod parse;
+mod per_iteration;
+mod shortest;
+mod signed;
+mod template;
+mod unit;
+
 /// Trait for providing `as_fractional_*` methods.
 ///
+/// The implementation for `Duration` (and anything that borrows as one)
+/// returns the `f64` (or `f32` for the `*_f32` methods) closest to the
+/// exact value (ties to even), even for very large durations.
+///
 /// # Examples
 ///
 /// ## Measuring a time span
@@ -83,95 +213,95 @@ pub trait TimeAsFloat {
     fn as_fractional_millis(&self) -> f64;
     /// Returns the duration in microseconds.
     fn as_fractional_micros(&self) -> f64;
+
+    /// Returns the duration in nanoseconds.
+    fn as_fractional_nanos(&self) -> f64 {
+        self.as_fractional_micros() * 1_000.0
+    }
+
+    /// Returns the duration in minutes.
+    fn as_fractional_minutes(&self) -> f64 {
+        self.as_fractional_secs() / 60.0
+    }
+
+    /// Returns the duration in hours.
+    fn as_fractional_hours(&self) -> f64 {
+        self.as_fractional_secs() / 3_600.0
+    }
+
+    /// Returns the duration in days.
+    fn as_fractional_days(&self) -> f64 {
+        self.as_fractional_secs() / 86_400.0
+    }
+
+    /// Returns the duration in the given `unit`.
+    fn as_fractional(&self, unit: TimeUnit) -> f64 {
+        match unit {
+            TimeUnit::Nanos => self.as_fractional_nanos(),
+            TimeUnit::Micros => self.as_fractional_micros(),
+            TimeUnit::Millis => self.as_fractional_millis(),
+            TimeUnit::Secs => self.as_fractional_secs(),
+            TimeUnit::Minutes => self.as_fractional_minutes(),
+            TimeUnit::Hours => self.as_fractional_hours(),
+            TimeUnit::Days => self.as_fractional_days(),
+        }
+    }
+
+    /// Returns the duration in seconds as `f32`.
+    fn as_fractional_secs_f32(&self) -> f32 {
+        self.as_fractional_f32(TimeUnit::Secs)
+    }
+
+    /// Returns the duration in milliseconds as `f32`.
+    fn as_fractional_millis_f32(&self) -> f32 {
+        self.as_fractional_f32(TimeUnit::Millis)
+    }
+
+    /// Returns the duration in microseconds as `f32`.
+    fn as_fractional_micros_f32(&self) -> f32 {
+        self.as_fractional_f32(TimeUnit::Micros)
+    }
+
+    /// Returns the duration in the given `unit` as `f32`.
+    fn as_fractional_f32(&self, unit: TimeUnit) -> f32 {
+        self.as_fractional(unit) as f32
+    }
 }
 
 impl<T: Borrow<Duration>> TimeAsFloat for T {
     fn as_fractional_secs(&self) -> f64 {
-        let dur: &Duration = self.borrow();
-
-        dur.as_secs() as f64 + dur.subsec_nanos() as f64 / 1_000_000_000.0
+        self.as_fractional(TimeUnit::Secs)
     }
 
     fn as_fractional_millis(&self) -> f64 {
-        let dur: &Duration = self.borrow();
-
-        dur.as_secs() as f64 * 1_000.0 + dur.subsec_nanos() as f64 / 1_000_000.0
+        self.as_fractional(TimeUnit::Millis)
     }
 
     fn as_fractional_micros(&self) -> f64 {
-        let dur: &Duration = self.borrow();
+        self.as_fractional(TimeUnit::Micros)
+    }
 
-        dur.as_secs() as f64 * 1_000_000.0 + dur.subsec_nanos() as f64 / 1_000.0
+    fn as_fractional_nanos(&self) -> f64 {
+        self.as_fractional(TimeUnit::Nanos)
     }
-}
 
-/// A formatting newtype for providing a
-/// [`Display`] implementation. This format is
-/// meant to be used for printing performance measurements.
-///
-/// # Behaviour
-///
-/// * `secs > 0` => seconds with up to 3 decimal places
-/// * `secs > 0.001` => milliseconds with up to 3 decimal places
-/// * `secs > 0.000_001` => microseconds with up to 3 decimal places
-/// * otherwise => nanoseconds
-///
-/// By default the duration is formatted using abbreviated units
-/// (e.g. `1.234ms`).
-/// If the the format string is specified with the [alternate flag] `{:#}`,
-/// the duration is formatted using the full unit name instead
-/// (e.g. `1.234 milliseconds`).
-///
-/// # Examples
-///
-/// ```
-/// use std::time::Duration;
-/// use floating_duration::TimeFormat;
-///
-/// let dur = Duration::new(0, 461_930);
-/// let formatted = format!("{}", TimeFormat(dur));
-/// assert_eq!(formatted, "461.93µs");
-/// let alternate = format!("{:#}", TimeFormat(dur));
-/// assert_eq!(alternate, "461.93 microseconds");
-/// ```
-///
-/// [`Display`]: https://doc.rust-lang.org/stable/std/fmt/trait.Display.html
-/// [alternate flag]: https://doc.rust-lang.org/stable/std/fmt/#sign0
-#[derive(Clone, Copy, Debug)]
-pub struct TimeFormat<T: Borrow<Duration>>(pub T);
-
-impl<T: Borrow<Duration>> Display for TimeFormat<T> {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
-        let dur: &Duration = self.0.borrow();
-
-        if dur.as_secs() > 0 {
-            if !f.alternate() {
-                write!(f, "{}s", round_3_decimals(dur.as_fractional_secs()))
-            } else {
-                write!(f, "{} seconds", round_3_decimals(dur.as_fractional_secs()))
-            }
-        } else if dur.subsec_nanos() > 1_000_000 {
-            if !f.alternate() {
-                write!(f, "{}ms", round_3_decimals(dur.as_fractional_millis()))
-            } else {
-                write!(f, "{} milliseconds", round_3_decimals(dur.as_fractional_millis()))
-            }
-        } else if dur.subsec_nanos() > 1_000 {
-            if !f.alternate() {
-                write!(f, "{}µs", round_3_decimals(dur.as_fractional_micros()))
-            } else {
-                write!(f, "{} microseconds", round_3_decimals(dur.as_fractional_micros()))
-            }
-        } else {
-            if !f.alternate() {
-                write!(f, "{}ns", dur.subsec_nanos())
-            } else {
-                write!(f, "{} nanoseconds", dur.subsec_nanos())
-            }
-        }
+    fn as_fractional_minutes(&self) -> f64 {
+        self.as_fractional(TimeUnit::Minutes)
+    }
+
+    fn as_fractional_hours(&self) -> f64 {
+        self.as_fractional(TimeUnit::Hours)
     }
-}
 
-fn round_3_decimals(x: f64) -> f64 {
-    (1000. * x).round() / 1000.
+    fn as_fractional_days(&self) -> f64 {
+        self.as_fractional(TimeUnit::Days)
+    }
+
+    fn as_fractional(&self, unit: TimeUnit) -> f64 {
+        exact::ratio_to_f64(exact::total_nanos(self.borrow()), unit.nanos())
+    }
+
+    fn as_fractional_f32(&self, unit: TimeUnit) -> f32 {
+        exact::ratio_to_f32(exact::total_nanos(self.borrow()), unit.nanos())
+    }
 }
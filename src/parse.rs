@@ -0,0 +1,468 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::error::Error;
+use std::fmt::{Display, Error as FormatError, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+use exact;
+use unit;
+use {TimeFormat, TimeUnit};
+
+/// Parses the output of [`TimeFormat`], e.g. `461.93µs`
+/// or `461.93 microseconds`.
+///
+/// `us` is accepted in place of `µs`. Decimal places below
+/// nanoseconds are rounded to the nearest nanosecond.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::TimeFormat;
+///
+/// let TimeFormat(dur) = "461.93µs".parse().unwrap();
+/// assert_eq!(dur, Duration::new(0, 461_930));
+///
+/// let TimeFormat(dur) = "1.5 seconds".parse().unwrap();
+/// assert_eq!(dur, Duration::new(1, 500_000_000));
+///
+/// let err = "12 parsecs".parse::<TimeFormat<Duration>>().unwrap_err();
+/// assert_eq!(err.offset(), 3);
+/// ```
+///
+/// [`TimeFormat`]: struct.TimeFormat.html
+impl FromStr for TimeFormat<Duration> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(s);
+        cursor.skip_whitespace();
+        if cursor.is_empty() {
+            return Err(cursor.error(ParseErrorKind::Empty));
+        }
+
+        cursor.eat('+');
+        let number = cursor.number()?;
+        cursor.skip_whitespace();
+        let unit = cursor.unit()?;
+        cursor.skip_whitespace();
+        cursor.end()?;
+
+        number.to_duration(unit).map(TimeFormat)
+    }
+}
+
+/// Parses a human-readable duration such as `250ms`, `1.5s`, `1h30m15.5s`
+/// or `2 minutes, 3 seconds`.
+///
+/// The input consists of one or more components, each a decimal number
+/// followed by a unit. Components may be adjacent or separated by
+/// whitespace or commas, and are summed up. Units can be abbreviated
+/// (`ns`, `us`/`µs`, `ms`, `s`, `m`, `h`, `d`), spelled out (`second`,
+/// `seconds`) or one of the common short forms `nsec`, `usec`, `msec`,
+/// `sec`, `min` and `hr` (optionally with an `s`).
+///
+/// An optional leading `+` or `-` sign is accepted, but since a `Duration`
+/// can't be negative, `-` is only valid for a zero duration.
+/// The sum is rounded to the nearest nanosecond once all components
+/// have been added.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::{parse_duration, ParseErrorKind};
+///
+/// assert_eq!(parse_duration("1h30m15.5s"), Ok(Duration::new(5_415, 500_000_000)));
+/// assert_eq!(parse_duration("250ms"), Ok(Duration::new(0, 250_000_000)));
+/// assert_eq!(parse_duration("2 minutes 3 seconds"), Ok(Duration::new(123, 0)));
+/// assert_eq!(parse_duration("+1.5 hrs"), Ok(Duration::new(5_400, 0)));
+/// assert_eq!(parse_duration("0.4ns 0.4ns"), Ok(Duration::new(0, 1)));
+///
+/// let err = parse_duration("1h 30").unwrap_err();
+/// assert_eq!(err.kind(), ParseErrorKind::MissingUnit);
+/// assert_eq!(err.offset(), 5);
+/// assert_eq!(err.to_string(), "expected a unit at byte 5");
+///
+/// assert_eq!(parse_duration("-2s").unwrap_err().kind(), ParseErrorKind::Negative);
+/// ```
+pub fn parse_duration(s: &str) -> Result<Duration, ParseError> {
+    let (sign, nanos) = parse_signed(s)?;
+
+    unsigned(sign, nanos)
+}
+
+/// Turns the result of a signed parser into a `Duration`,
+/// failing if it is negative.
+pub fn unsigned(sign: Option<usize>, nanos: u128) -> Result<Duration, ParseError> {
+    match sign {
+        Some(offset) if nanos > 0 => Err(ParseError {
+            kind: ParseErrorKind::Negative,
+            offset,
+        }),
+        _ => Ok(exact::duration_from_nanos(nanos).expect("checked by the parser")),
+    }
+}
+
+/// Parses the input of `parse_duration`, returning the offset of the minus
+/// sign if there is one, and the total number of nanoseconds (which fits
+/// into a `Duration`).
+pub fn parse_signed(s: &str) -> Result<(Option<usize>, u128), ParseError> {
+    let mut cursor = Cursor::new(s);
+    cursor.skip_whitespace();
+    if cursor.is_empty() {
+        return Err(cursor.error(ParseErrorKind::Empty));
+    }
+
+    let sign = cursor.sign();
+    cursor.skip_whitespace();
+
+    let mut total = ExactNanos::default();
+    loop {
+        let number = cursor.number()?;
+        cursor.skip_whitespace();
+        let unit = cursor.unit()?;
+
+        total = number
+            .to_exact(unit.nanos())?
+            .checked_add(total)
+            .and_then(|total| exact::duration_from_nanos(total.round()).map(|_| total))
+            .ok_or(ParseError {
+                kind: ParseErrorKind::OutOfRange,
+                offset: number.offset,
+            })?;
+
+        cursor.skip_whitespace();
+        if cursor.is_empty() {
+            break;
+        }
+        if cursor.eat(',') {
+            cursor.skip_whitespace();
+        } else if !cursor
+            .rest()
+            .starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        {
+            return Err(cursor.error(ParseErrorKind::UnexpectedCharacter));
+        }
+    }
+
+    Ok((sign, total.round()))
+}
+
+/// The error returned when parsing a duration fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    offset: usize,
+}
+
+impl ParseError {
+    /// Returns what went wrong.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset in the input at which the problem was found.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        write!(f, "{} at byte {}", self.kind, self.offset)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Creates an error at the given byte offset.
+pub fn error_at(kind: ParseErrorKind, offset: usize) -> ParseError {
+    ParseError { kind, offset }
+}
+
+/// The kind of a [`ParseError`].
+///
+/// [`ParseError`]: struct.ParseError.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+    /// The input was empty.
+    Empty,
+    /// A number was expected.
+    InvalidNumber,
+    /// A unit was expected.
+    MissingUnit,
+    /// The unit isn't known.
+    UnknownUnit,
+    /// A character was not expected at this position.
+    UnexpectedCharacter,
+    /// The duration is too large to be represented by a `Duration`.
+    OutOfRange,
+    /// The duration is negative.
+    Negative,
+    /// A calendar unit (years or months) was used, which doesn't
+    /// have a fixed length.
+    CalendarUnit,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let msg = match *self {
+            ParseErrorKind::Empty => "empty input",
+            ParseErrorKind::InvalidNumber => "expected a number",
+            ParseErrorKind::MissingUnit => "expected a unit",
+            ParseErrorKind::UnknownUnit => "unknown unit",
+            ParseErrorKind::UnexpectedCharacter => "unexpected character",
+            ParseErrorKind::OutOfRange => "duration out of range",
+            ParseErrorKind::Negative => "duration is negative",
+            ParseErrorKind::CalendarUnit => "calendar units are not allowed",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+/// A position in the input, used by all parsers.
+pub struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Cursor { input, pos: 0 }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    pub fn offset(&self) -> usize {
+        self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    pub fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            kind,
+            offset: self.pos,
+        }
+    }
+
+    /// Fails unless the whole input has been consumed.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(ParseErrorKind::UnexpectedCharacter))
+        }
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consumes an optional `+` or `-` sign,
+    /// returning the offset of the latter.
+    pub fn sign(&mut self) -> Option<usize> {
+        let offset = self.pos;
+        if self.eat('-') {
+            Some(offset)
+        } else {
+            self.eat('+');
+            None
+        }
+    }
+
+    /// Consumes the next character.
+    pub fn bump(&mut self) -> Option<char> {
+        let c = self.rest().chars().next();
+        if let Some(c) = c {
+            self.pos += c.len_utf8();
+        }
+
+        c
+    }
+
+    /// Consumes `c` if it is next.
+    pub fn eat(&mut self, c: char) -> bool {
+        if self.rest().starts_with(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes the longest prefix whose characters match `pred`.
+    pub fn take_while<F: Fn(char) -> bool>(&mut self, pred: F) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c: char| !pred(c)).unwrap_or(rest.len());
+        self.pos += len;
+
+        &rest[..len]
+    }
+
+    /// Parses a decimal number like `12`, `12.5` or `.5`.
+    pub fn number(&mut self) -> Result<Number<'a>, ParseError> {
+        self.number_with(&['.'])
+    }
+
+    /// Parses a decimal number with any of the given decimal separators.
+    pub fn number_with(&mut self, separators: &[char]) -> Result<Number<'a>, ParseError> {
+        let offset = self.pos;
+        let int = self.take_while(|c| c.is_ascii_digit());
+        let frac = if separators.iter().any(|&sep| self.eat(sep)) {
+            self.take_while(|c| c.is_ascii_digit())
+        } else {
+            ""
+        };
+
+        if int.is_empty() && frac.is_empty() {
+            self.pos = offset;
+            return Err(self.error(ParseErrorKind::InvalidNumber));
+        }
+
+        Ok(Number { int, frac, offset })
+    }
+
+    /// Parses a unit name like `ms` or `milliseconds`.
+    pub fn unit(&mut self) -> Result<TimeUnit, ParseError> {
+        let offset = self.pos;
+        let name = self.take_while(char::is_alphabetic);
+        if name.is_empty() {
+            return Err(self.error(ParseErrorKind::MissingUnit));
+        }
+
+        unit::from_name(name).ok_or(ParseError {
+            kind: ParseErrorKind::UnknownUnit,
+            offset,
+        })
+    }
+}
+
+/// A parsed decimal number, split at the decimal point.
+pub struct Number<'a> {
+    int: &'a str,
+    frac: &'a str,
+    offset: usize,
+}
+
+impl<'a> Number<'a> {
+    /// Returns the byte offset of the number in the input.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the exact amount of time this number stands for in a unit.
+    pub fn to_exact(&self, unit_nanos: u64) -> Result<ExactNanos, ParseError> {
+        let out_of_range = ParseError {
+            kind: ParseErrorKind::OutOfRange,
+            offset: self.offset,
+        };
+        let unit_nanos = unit_nanos as u128;
+
+        let mut int: u128 = 0;
+        for digit in self.int.bytes() {
+            int = int
+                .checked_mul(10)
+                .and_then(|int| int.checked_add((digit - b'0') as u128))
+                .ok_or(out_of_range)?;
+        }
+        let int_nanos = int.checked_mul(unit_nanos).ok_or(out_of_range)?;
+
+        // Split the unit into `multiple * 10^places`; the first `places`
+        // decimals are whole nanoseconds, the rest is a fraction of one.
+        let places = exact::max_decimals(unit_nanos as u64);
+        let multiple = unit_nanos / 10u128.pow(places as u32);
+        let (whole, rest) = if self.frac.len() > places {
+            self.frac.split_at(places)
+        } else {
+            (self.frac, "")
+        };
+        let rest = &rest[..rest.len().min(SUB_DIGITS)];
+
+        let whole = digits_value(whole) * 10u128.pow((places - whole.len()) as u32);
+        let sub = digits_value(rest) * multiple * 10u128.pow((SUB_DIGITS - rest.len()) as u32);
+
+        ExactNanos {
+            whole: int_nanos
+                .checked_add(whole * multiple)
+                .ok_or(out_of_range)?,
+            sub: 0,
+        }
+        .checked_add(ExactNanos { whole: 0, sub })
+        .ok_or(out_of_range)
+    }
+
+    /// Returns the number of nanoseconds, rounded to the nearest nanosecond
+    /// (ties away from zero).
+    pub fn to_nanos(&self, unit_nanos: u64) -> Result<u128, ParseError> {
+        Ok(self.to_exact(unit_nanos)?.round())
+    }
+
+    /// Returns the number in `unit` as a `Duration`.
+    pub fn to_duration(&self, unit: TimeUnit) -> Result<Duration, ParseError> {
+        let nanos = self.to_nanos(unit.nanos())?;
+
+        exact::duration_from_nanos(nanos).ok_or(ParseError {
+            kind: ParseErrorKind::OutOfRange,
+            offset: self.offset,
+        })
+    }
+}
+
+/// The number of decimal places kept below one nanosecond.
+const SUB_DIGITS: usize = 24;
+
+/// An amount of time in nanoseconds, kept exact up to `SUB_DIGITS`
+/// decimal places so parts can be added before rounding.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExactNanos {
+    whole: u128,
+    /// The fractional nanoseconds, scaled by `10^SUB_DIGITS`.
+    sub: u128,
+}
+
+impl ExactNanos {
+    pub fn checked_add(self, other: ExactNanos) -> Option<ExactNanos> {
+        let scale = 10u128.pow(SUB_DIGITS as u32);
+        let sub = self.sub + other.sub;
+
+        self.whole
+            .checked_add(other.whole)
+            .and_then(|whole| whole.checked_add(sub / scale))
+            .map(|whole| ExactNanos {
+                whole,
+                sub: sub % scale,
+            })
+    }
+
+    /// Rounds to the nearest nanosecond (ties away from zero).
+    pub fn round(self) -> u128 {
+        let half = 10u128.pow(SUB_DIGITS as u32) / 2;
+
+        if self.sub >= half {
+            self.whole.saturating_add(1)
+        } else {
+            self.whole
+        }
+    }
+}
+
+/// The value of at most `SUB_DIGITS` decimal digits.
+fn digits_value(digits: &str) -> u128 {
+    digits
+        .bytes()
+        .fold(0, |acc, digit| acc * 10 + (digit - b'0') as u128)
+}
@@ -0,0 +1,162 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Borrow;
+use std::fmt::{Display, Error as FormatError, Formatter, Write};
+use std::time::Duration;
+
+use exact;
+use format::pad;
+use unit::{self, NAMES, PICOS};
+use {RoundingMode, TimeFormat, TimeUnit};
+
+/// The units picked for a single iteration, from the largest to the
+/// smallest; `None` stands for picoseconds.
+const SCALES: [Option<TimeUnit>; 5] = [
+    Some(TimeUnit::Secs),
+    Some(TimeUnit::Millis),
+    Some(TimeUnit::Micros),
+    Some(TimeUnit::Nanos),
+    None,
+];
+
+impl<T> TimeFormat<T> {
+    /// Creates a formatter for the time of a single iteration, if
+    /// `iterations` runs took `total` together.
+    ///
+    /// The division is exact, so the result isn't limited to whole
+    /// nanoseconds: below one nanosecond, the value is printed in
+    /// picoseconds. Otherwise it's printed like by `TimeFormat`, with
+    /// up to 3 decimal places or as many as the precision specifies, and
+    /// rounded half up unless a different [rounding mode] is set. Unlike
+    /// `TimeFormat`, it doesn't take a [`TimeFormatStyle`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `iterations` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use floating_duration::TimeFormat;
+    ///
+    /// let total = Duration::new(0, 350_000_000);
+    /// let iterations = 1_000_000_000;
+    ///
+    /// assert_eq!(format!("{}", TimeFormat::per_iteration(total, iterations)), "350ps");
+    /// assert_eq!(format!("{:#}", TimeFormat::per_iteration(total, 1_000)), "350 microseconds");
+    ///
+    /// let total = Duration::new(1, 270_000_000);
+    /// assert_eq!(format!("{}", TimeFormat::per_iteration(total, iterations)), "1.27ns");
+    /// assert_eq!(format!("{:.1}", TimeFormat::per_iteration(total, 3)), "423.3ms");
+    /// ```
+    ///
+    /// [rounding mode]: struct.PerIterationFormat.html#method.rounding
+    /// [`TimeFormatStyle`]: struct.TimeFormatStyle.html
+    pub fn per_iteration(total: T, iterations: u64) -> PerIterationFormat<T> {
+        assert!(iterations > 0, "number of iterations is zero");
+
+        PerIterationFormat {
+            total,
+            iterations,
+            rounding: RoundingMode::HalfUp,
+        }
+    }
+}
+
+/// The time of a single iteration, created by
+/// [`TimeFormat::per_iteration`].
+///
+/// [`TimeFormat::per_iteration`]: struct.TimeFormat.html#method.per_iteration
+#[derive(Clone, Copy, Debug)]
+pub struct PerIterationFormat<T> {
+    total: T,
+    iterations: u64,
+    rounding: RoundingMode,
+}
+
+impl<T: Borrow<Duration>> PerIterationFormat<T> {
+    /// Sets how the value is rounded to the printed number of
+    /// decimal places; the default is [`RoundingMode::HalfUp`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use floating_duration::{RoundingMode, TimeFormat};
+    ///
+    /// let fmt = TimeFormat::per_iteration(Duration::new(2, 0), 3);
+    /// assert_eq!(format!("{:.1}", fmt), "666.7ms");
+    /// assert_eq!(format!("{:.1}", fmt.rounding(RoundingMode::Truncate)), "666.6ms");
+    /// ```
+    ///
+    /// [`RoundingMode::HalfUp`]: enum.RoundingMode.html#variant.HalfUp
+    pub fn rounding(mut self, mode: RoundingMode) -> Self {
+        self.rounding = mode;
+
+        self
+    }
+
+    fn write_unpadded<W: Write>(
+        &self,
+        w: &mut W,
+        alternate: bool,
+        precision: Option<usize>,
+    ) -> Result<(), FormatError> {
+        let picos = exact::total_nanos(self.total.borrow()) * 1_000;
+        let decimals = precision.unwrap_or(3);
+
+        let den = |scale: Option<TimeUnit>| {
+            let unit_picos = scale.map_or(1, |unit| unit.nanos() as u128 * 1_000);
+
+            unit_picos * self.iterations as u128
+        };
+
+        // Like for `TimeFormat`, the unit is picked for the exact value,
+        // and zero is printed in nanoseconds.
+        let smallest = if picos == 0 {
+            SCALES.len() - 2
+        } else {
+            SCALES.len() - 1
+        };
+        let mut index = SCALES
+            .iter()
+            .position(|&scale| picos >= den(scale))
+            .unwrap_or(smallest);
+        let (mut int, mut fraction) =
+            exact::divide_decimal(picos, den(SCALES[index]), decimals, self.rounding);
+        // Rounding may carry over into the next unit, e.g. 999.9996ps to 1ns.
+        if index > 0 && int >= 1_000 {
+            index -= 1;
+            let rounded = exact::divide_decimal(picos, den(SCALES[index]), decimals, self.rounding);
+            int = rounded.0;
+            fraction = rounded.1;
+        }
+        let scale = SCALES[index];
+
+        let mut value = int.to_string();
+        exact::write_digits(&mut value, &fraction, precision)?;
+
+        let names = scale.map_or(PICOS, |unit| NAMES[unit as usize]);
+        if !alternate {
+            write!(w, "{}{}", value, names.0)
+        } else {
+            write!(w, "{} {}", value, unit::name_for(names, &value))
+        }
+    }
+}
+
+impl<T: Borrow<Duration>> Display for PerIterationFormat<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let mut buf = String::new();
+        self.write_unpadded(&mut buf, f.alternate(), f.precision())?;
+
+        pad(f, &buf)
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Borrow;
+use std::fmt::{Display, Error as FormatError, Formatter, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use exact;
+use format::pad;
+use unit::UNITS;
+use {ParseError, TimeFormat, TimeUnit};
+
+/// The most decimal places needed by any unit; with them, even
+/// a day is split into steps of less than half a nanosecond.
+const MAX_DECIMALS: usize = 15;
+
+/// A formatting newtype which prints the shortest string that
+/// parses back to exactly the same duration, e.g. `1.5s` or
+/// `1.000000001s`.
+///
+/// # Behaviour
+///
+/// Of all units in which the value is at least one (nanoseconds up
+/// to days), the one with the shortest output is chosen, preferring
+/// the larger unit on a tie. Within that unit, the value has as few
+/// decimal places as possible while still [parsing] back to the same
+/// number of nanoseconds. Zero is printed as `0s`.
+///
+/// With the [alternate flag] `{:#}`, the unit is spelled out.
+/// Width, fill, alignment and the `+` flag are honored like for
+/// [`TimeFormat`]; the precision is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::ShortestFormat;
+///
+/// let fmt = |secs, nanos| format!("{}", ShortestFormat(Duration::new(secs, nanos)));
+///
+/// assert_eq!(fmt(1, 500_000_000), "1.5s");
+/// assert_eq!(fmt(1, 1), "1.000000001s");
+/// assert_eq!(fmt(0, 100_000_000), "100ms");
+/// assert_eq!(fmt(90, 0), "90s");
+/// assert_eq!(fmt(5_400, 0), "90m");
+///
+/// let ShortestFormat(dur) = "90m".parse().unwrap();
+/// assert_eq!(dur, Duration::new(5_400, 0));
+/// ```
+///
+/// [parsing]: #impl-FromStr
+/// [alternate flag]: https://doc.rust-lang.org/stable/std/fmt/#sign0
+/// [`TimeFormat`]: struct.TimeFormat.html
+#[derive(Clone, Copy, Debug)]
+pub struct ShortestFormat<T: Borrow<Duration>>(pub T);
+
+impl<T: Borrow<Duration>> ShortestFormat<T> {
+    fn write_unpadded<W: Write>(&self, w: &mut W, alternate: bool) -> Result<(), FormatError> {
+        let nanos = exact::total_nanos(self.0.borrow());
+
+        // Candidates are checked from the smallest unit up,
+        // so a larger unit wins a tie.
+        let mut best = (String::from("0"), TimeUnit::Secs, usize::max_value());
+        for &unit in UNITS.iter().filter(|unit| nanos >= unit.nanos() as u128) {
+            if let Some(value) = shortest_value(nanos, unit.nanos() as u128) {
+                let len = value.len() + unit.abbreviation().chars().count();
+                if len <= best.2 {
+                    best = (value, unit, len);
+                }
+            }
+        }
+        let (value, unit) = (&best.0, best.1);
+
+        if !alternate {
+            write!(w, "{}{}", value, unit.abbreviation())
+        } else {
+            write!(w, "{} {}", value, unit.name_for(value))
+        }
+    }
+}
+
+impl<T: Borrow<Duration>> Display for ShortestFormat<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let mut buf = String::new();
+        self.write_unpadded(&mut buf, f.alternate())?;
+
+        pad(f, &buf)
+    }
+}
+
+/// Parses a single number with a unit, like the output of
+/// `ShortestFormat` or [`TimeFormat`].
+///
+/// [`TimeFormat`]: struct.TimeFormat.html#impl-FromStr
+impl FromStr for ShortestFormat<Duration> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        s.parse().map(|TimeFormat(dur)| ShortestFormat(dur))
+    }
+}
+
+/// Returns the decimal with the fewest places which,
+/// in a unit of `unit_nanos`, rounds back to `nanos`.
+fn shortest_value(nanos: u128, unit_nanos: u128) -> Option<String> {
+    for places in 0..=MAX_DECIMALS {
+        let pow = 10u128.pow(places as u32);
+        let scaled = nanos.checked_mul(pow)?;
+
+        let below = scaled / unit_nanos;
+        let candidates = if (scaled % unit_nanos) * 2 < unit_nanos {
+            [below, below + 1]
+        } else {
+            [below + 1, below]
+        };
+        for &candidate in &candidates {
+            // Parsing rounds ties away from zero, like `round_decimal`.
+            let back = candidate
+                .checked_mul(unit_nanos)
+                .and_then(|n| n.checked_add(pow / 2))
+                .map(|n| n / pow);
+            if back == Some(nanos) {
+                let mut value = String::new();
+                exact::write_decimal(&mut value, candidate, places, Some(places)).ok()?;
+
+                return Some(value);
+            }
+        }
+    }
+
+    None
+}
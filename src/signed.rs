@@ -0,0 +1,220 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Error as FormatError, Formatter};
+use std::ops::Neg;
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime};
+
+use exact;
+use parse;
+use {ParseError, TimeAsFloat, TimeFormat, TimeUnit};
+
+/// A duration which can be negative, e.g. clock skew
+/// or the slack before a deadline.
+///
+/// It supports the [`TimeAsFloat`] conversions, which return negative
+/// values for negative durations, and is printed like [`TimeFormat`]
+/// with a leading `-` if needed (also with a [style]).
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::{SignedDuration, TimeAsFloat, TimeFormat};
+///
+/// let skew = -SignedDuration::from(Duration::new(0, 1_234_000));
+///
+/// assert!(skew.is_negative());
+/// assert_eq!(skew.abs(), Duration::new(0, 1_234_000));
+/// assert_eq!(skew.as_fractional_millis(), -1.234);
+/// assert_eq!(format!("{}", skew), "-1.234ms");
+/// assert_eq!(format!("{:#.1}", TimeFormat(skew)), "-1.2 milliseconds");
+///
+/// let parsed: SignedDuration = "-1.234ms".parse().unwrap();
+/// assert_eq!(parsed, skew);
+/// ```
+///
+/// [`TimeAsFloat`]: trait.TimeAsFloat.html
+/// [`TimeFormat`]: struct.TimeFormat.html
+/// [style]: struct.TimeFormat.html#method.with_style
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SignedDuration {
+    negative: bool,
+    abs: Duration,
+}
+
+impl SignedDuration {
+    /// Creates a signed duration from its sign and magnitude.
+    ///
+    /// A zero duration is never negative.
+    pub fn new(negative: bool, abs: Duration) -> Self {
+        SignedDuration {
+            negative: negative && abs != Duration::new(0, 0),
+            abs,
+        }
+    }
+
+    /// Returns `true` if the duration is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Returns the magnitude of the duration.
+    pub fn abs(&self) -> Duration {
+        self.abs
+    }
+}
+
+impl From<Duration> for SignedDuration {
+    fn from(dur: Duration) -> Self {
+        SignedDuration::new(false, dur)
+    }
+}
+
+impl Neg for SignedDuration {
+    type Output = SignedDuration;
+
+    fn neg(self) -> SignedDuration {
+        SignedDuration::new(!self.negative, self.abs)
+    }
+}
+
+impl Ord for SignedDuration {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.abs.cmp(&other.abs),
+            (true, true) => other.abs.cmp(&self.abs),
+            (negative, _) => {
+                if negative {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            }
+        }
+    }
+}
+
+impl PartialOrd for SignedDuration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl TimeAsFloat for SignedDuration {
+    fn as_fractional_secs(&self) -> f64 {
+        self.as_fractional(TimeUnit::Secs)
+    }
+
+    fn as_fractional_millis(&self) -> f64 {
+        self.as_fractional(TimeUnit::Millis)
+    }
+
+    fn as_fractional_micros(&self) -> f64 {
+        self.as_fractional(TimeUnit::Micros)
+    }
+
+    fn as_fractional_nanos(&self) -> f64 {
+        self.as_fractional(TimeUnit::Nanos)
+    }
+
+    fn as_fractional_minutes(&self) -> f64 {
+        self.as_fractional(TimeUnit::Minutes)
+    }
+
+    fn as_fractional_hours(&self) -> f64 {
+        self.as_fractional(TimeUnit::Hours)
+    }
+
+    fn as_fractional_days(&self) -> f64 {
+        self.as_fractional(TimeUnit::Days)
+    }
+
+    fn as_fractional(&self, unit: TimeUnit) -> f64 {
+        let abs = self.abs.as_fractional(unit);
+
+        if self.negative {
+            -abs
+        } else {
+            abs
+        }
+    }
+
+    fn as_fractional_f32(&self, unit: TimeUnit) -> f32 {
+        let abs = self.abs.as_fractional_f32(unit);
+
+        if self.negative {
+            -abs
+        } else {
+            abs
+        }
+    }
+}
+
+impl Display for SignedDuration {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        TimeFormat(*self).fmt(f)
+    }
+}
+
+/// Parses the input of [`parse_duration`], but also
+/// accepts negative durations, e.g. `-1.5s` or `-1h 30m`.
+///
+/// [`parse_duration`]: fn.parse_duration.html
+impl FromStr for SignedDuration {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (sign, nanos) = parse::parse_signed(s)?;
+        let abs = exact::duration_from_nanos(nanos).expect("checked by the parser");
+
+        Ok(SignedDuration::new(sign.is_some(), abs))
+    }
+}
+
+/// Trait for computing the signed difference between two points in time.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::{Duration, Instant};
+/// use floating_duration::SignedDurationSince;
+///
+/// let start = Instant::now();
+/// let deadline = start + Duration::new(1, 0);
+///
+/// assert!(!deadline.signed_duration_since(start).is_negative());
+/// assert!(start.signed_duration_since(deadline).is_negative());
+/// assert_eq!(start.signed_duration_since(deadline).abs(), Duration::new(1, 0));
+/// ```
+pub trait SignedDurationSince {
+    /// Returns `self - earlier`, which is negative
+    /// if `earlier` is actually later than `self`.
+    fn signed_duration_since(&self, earlier: Self) -> SignedDuration;
+}
+
+impl SignedDurationSince for Instant {
+    fn signed_duration_since(&self, earlier: Self) -> SignedDuration {
+        if *self >= earlier {
+            SignedDuration::new(false, self.duration_since(earlier))
+        } else {
+            SignedDuration::new(true, earlier.duration_since(*self))
+        }
+    }
+}
+
+impl SignedDurationSince for SystemTime {
+    fn signed_duration_since(&self, earlier: Self) -> SignedDuration {
+        match self.duration_since(earlier) {
+            Ok(dur) => SignedDuration::new(false, dur),
+            Err(err) => SignedDuration::new(true, err.duration()),
+        }
+    }
+}
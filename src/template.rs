@@ -0,0 +1,375 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt::{Display, Error as FormatError, Formatter, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use exact;
+use format::pad;
+use {RoundingMode, TimeUnit};
+
+/// The largest width a directive may request.
+const MAX_WIDTH: usize = 64;
+
+/// A compiled, `strftime`-like pattern for formatting durations,
+/// e.g. `%H:%M:%S.%3f`.
+///
+/// # Directives
+///
+/// | Unit         | Total | Remainder     |
+/// |--------------|-------|---------------|
+/// | days         | `%d`  | `%d`          |
+/// | hours        | `%h`  | `%H` (00-23)  |
+/// | minutes      | `%m`  | `%M` (00-59)  |
+/// | seconds      | `%s`  | `%S` (00-59)  |
+/// | milliseconds | `%l`  | `%L` (000-999)|
+/// | microseconds | `%u`  | `%U` (000-999)|
+/// | nanoseconds  | `%n`  | `%N` (000-999)|
+///
+/// A total is the whole duration in that unit, a remainder is what is
+/// left over after taking away the next larger unit. Remainders are
+/// zero-padded to their natural width, totals aren't padded.
+///
+/// A width can be given after the `%` (`%3h`), and padding can be
+/// changed with a `-` (none) or `_` (spaces) flag, e.g. `%-M` or `%_4s`.
+///
+/// `%f` prints the fraction of a second, with as many digits as the
+/// width (9 by default), so `%3f` prints milliseconds. `%%` is a
+/// literal `%`, any other text is copied as is.
+///
+/// The duration is rounded to the finest field of the pattern (ties
+/// away from zero, unless another [rounding mode] is set) before any
+/// field is computed, so `59.9996s` with `%M:%S.%3f` is `01:00.000`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::DurationTemplate;
+///
+/// let dur = Duration::new(3_723, 456_789_000);
+///
+/// let template = DurationTemplate::new("%H:%M:%S.%3f").unwrap();
+/// assert_eq!(template.format(dur).to_string(), "01:02:03.457");
+///
+/// let template = DurationTemplate::new("%s.%6f s").unwrap();
+/// assert_eq!(template.format(dur).to_string(), "3723.456789 s");
+///
+/// let template: DurationTemplate = "%mm%Ss".parse().unwrap();
+/// assert_eq!(template.format(dur).to_string(), "62m03s");
+///
+/// let err = DurationTemplate::new("%H:%Q").unwrap_err();
+/// assert_eq!(err.offset(), 4);
+/// ```
+///
+/// [rounding mode]: #method.rounding
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DurationTemplate {
+    items: Vec<Item>,
+    resolution: u64,
+    rounding: RoundingMode,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Item {
+    Literal(String),
+    Field {
+        unit: TimeUnit,
+        total: bool,
+        padding: Padding,
+        width: Option<usize>,
+    },
+    Fraction(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Padding {
+    Zeros,
+    Spaces,
+    None,
+}
+
+impl DurationTemplate {
+    /// Compiles a pattern, see the [type documentation] for the syntax.
+    ///
+    /// [type documentation]: #directives
+    pub fn new(pattern: &str) -> Result<Self, TemplateError> {
+        let mut items = Vec::new();
+        let mut literal = String::new();
+        let mut chars = pattern.char_indices().peekable();
+
+        while let Some((_, c)) = chars.next() {
+            if c != '%' {
+                literal.push(c);
+                continue;
+            }
+
+            let padding = match chars.peek() {
+                Some(&(_, '-')) => Some(Padding::None),
+                Some(&(_, '_')) => Some(Padding::Spaces),
+                _ => None,
+            };
+            if padding.is_some() {
+                chars.next();
+            }
+
+            let mut width = None;
+            while let Some(&(offset, c)) = chars.peek() {
+                let digit = match c.to_digit(10) {
+                    Some(digit) => digit as usize,
+                    None => break,
+                };
+                let new = width.unwrap_or(0) * 10 + digit;
+                if new > MAX_WIDTH {
+                    return Err(error_at(TemplateErrorKind::InvalidWidth, offset));
+                }
+                width = Some(new);
+                chars.next();
+            }
+
+            let (offset, directive) = match chars.next() {
+                Some(next) => next,
+                None => return Err(error_at(TemplateErrorKind::MissingDirective, pattern.len())),
+            };
+            let (unit, total) = match directive {
+                '%' if padding.is_none() && width.is_none() => {
+                    literal.push('%');
+                    continue;
+                }
+                'f' => {
+                    if !literal.is_empty() {
+                        items.push(Item::Literal(literal.split_off(0)));
+                    }
+                    items.push(Item::Fraction(width.unwrap_or(9)));
+                    continue;
+                }
+                'd' => (TimeUnit::Days, true),
+                'h' => (TimeUnit::Hours, true),
+                'H' => (TimeUnit::Hours, false),
+                'm' => (TimeUnit::Minutes, true),
+                'M' => (TimeUnit::Minutes, false),
+                's' => (TimeUnit::Secs, true),
+                'S' => (TimeUnit::Secs, false),
+                'l' => (TimeUnit::Millis, true),
+                'L' => (TimeUnit::Millis, false),
+                'u' => (TimeUnit::Micros, true),
+                'U' => (TimeUnit::Micros, false),
+                'n' => (TimeUnit::Nanos, true),
+                'N' => (TimeUnit::Nanos, false),
+                _ => return Err(error_at(TemplateErrorKind::UnknownDirective, offset)),
+            };
+
+            if !literal.is_empty() {
+                items.push(Item::Literal(literal.split_off(0)));
+            }
+            items.push(Item::Field {
+                unit,
+                total,
+                padding: padding.unwrap_or(Padding::Zeros),
+                width,
+            });
+        }
+        if !literal.is_empty() {
+            items.push(Item::Literal(literal));
+        }
+
+        let resolution = items
+            .iter()
+            .filter_map(|item| match *item {
+                Item::Literal(_) => None,
+                Item::Field { unit, .. } => Some(unit.nanos()),
+                Item::Fraction(digits) => Some(10u64.pow(9 - digits.min(9) as u32)),
+            })
+            .min()
+            .unwrap_or(1);
+
+        Ok(DurationTemplate {
+            items,
+            resolution,
+            rounding: RoundingMode::HalfUp,
+        })
+    }
+
+    /// Sets how the duration is rounded to the finest field,
+    /// which defaults to `RoundingMode::HalfUp`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use floating_duration::{DurationTemplate, RoundingMode};
+    ///
+    /// let template = DurationTemplate::new("%M:%S").unwrap();
+    /// let dur = Duration::new(59, 500_000_000);
+    /// assert_eq!(template.format(dur).to_string(), "01:00");
+    ///
+    /// let template = template.rounding(RoundingMode::Floor);
+    /// assert_eq!(template.format(dur).to_string(), "00:59");
+    /// ```
+    pub fn rounding(mut self, mode: RoundingMode) -> Self {
+        self.rounding = mode;
+
+        self
+    }
+
+    /// Returns a value which formats `dur` according to this template
+    /// when displayed.
+    pub fn format<T: Borrow<Duration>>(&self, dur: T) -> TemplateFormat<'_, T> {
+        TemplateFormat {
+            template: self,
+            dur,
+        }
+    }
+}
+
+impl FromStr for DurationTemplate {
+    type Err = TemplateError;
+
+    fn from_str(s: &str) -> Result<Self, TemplateError> {
+        DurationTemplate::new(s)
+    }
+}
+
+/// A duration formatted with a [`DurationTemplate`].
+///
+/// Width, fill, alignment and the `+` flag are honored
+/// like for [`TimeFormat`].
+///
+/// [`DurationTemplate`]: struct.DurationTemplate.html
+/// [`TimeFormat`]: struct.TimeFormat.html
+#[derive(Clone, Copy, Debug)]
+pub struct TemplateFormat<'a, T: Borrow<Duration>> {
+    template: &'a DurationTemplate,
+    dur: T,
+}
+
+impl<'a, T: Borrow<Duration>> TemplateFormat<'a, T> {
+    fn write_unpadded<W: Write>(&self, w: &mut W) -> Result<(), FormatError> {
+        let resolution = self.template.resolution;
+        let nanos = exact::total_nanos(self.dur.borrow());
+        let nanos = exact::round_decimal(nanos, resolution, 0, self.template.rounding).0
+            * resolution as u128;
+
+        for item in &self.template.items {
+            match *item {
+                Item::Literal(ref s) => w.write_str(s)?,
+                Item::Field {
+                    unit,
+                    total,
+                    padding,
+                    width,
+                } => {
+                    let value = nanos / unit.nanos() as u128;
+                    let (value, natural) = match modulus(unit) {
+                        Some(modulus) if !total => {
+                            (value % modulus, (modulus - 1).to_string().len())
+                        }
+                        _ => (value, 1),
+                    };
+                    let width = width.unwrap_or(natural);
+
+                    match padding {
+                        Padding::Zeros => write!(w, "{:01$}", value, width)?,
+                        Padding::Spaces => write!(w, "{:1$}", value, width)?,
+                        Padding::None => write!(w, "{}", value)?,
+                    }
+                }
+                Item::Fraction(digits) => {
+                    let fraction = format!("{:09}", nanos % 1_000_000_000);
+                    if digits <= 9 {
+                        w.write_str(&fraction[..digits])?;
+                    } else {
+                        write!(w, "{:0<1$}", fraction, digits)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, T: Borrow<Duration>> Display for TemplateFormat<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let mut buf = String::new();
+        self.write_unpadded(&mut buf)?;
+
+        pad(f, &buf)
+    }
+}
+
+/// How many of `unit` make up the next larger unit.
+fn modulus(unit: TimeUnit) -> Option<u128> {
+    match unit {
+        TimeUnit::Nanos | TimeUnit::Micros | TimeUnit::Millis => Some(1_000),
+        TimeUnit::Secs | TimeUnit::Minutes => Some(60),
+        TimeUnit::Hours => Some(24),
+        TimeUnit::Days => None,
+    }
+}
+
+/// The error returned when compiling a [`DurationTemplate`] fails.
+///
+/// [`DurationTemplate`]: struct.DurationTemplate.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TemplateError {
+    kind: TemplateErrorKind,
+    offset: usize,
+}
+
+impl TemplateError {
+    /// Returns what went wrong.
+    pub fn kind(&self) -> TemplateErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset in the pattern at which the problem was found.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        write!(f, "{} at byte {}", self.kind, self.offset)
+    }
+}
+
+impl Error for TemplateError {}
+
+fn error_at(kind: TemplateErrorKind, offset: usize) -> TemplateError {
+    TemplateError { kind, offset }
+}
+
+/// The kind of a [`TemplateError`].
+///
+/// [`TemplateError`]: struct.TemplateError.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemplateErrorKind {
+    /// The pattern ended after a `%`.
+    MissingDirective,
+    /// The character after a `%` isn't a known directive.
+    UnknownDirective,
+    /// The width of a directive is too large.
+    InvalidWidth,
+}
+
+impl Display for TemplateErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FormatError> {
+        let msg = match *self {
+            TemplateErrorKind::MissingDirective => "expected a directive",
+            TemplateErrorKind::UnknownDirective => "unknown directive",
+            TemplateErrorKind::InvalidWidth => "width is too large",
+        };
+
+        f.write_str(msg)
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2017 Thomas Schaller.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// All units, from the smallest to the largest.
+pub const UNITS: [TimeUnit; 7] = [
+    TimeUnit::Nanos,
+    TimeUnit::Micros,
+    TimeUnit::Millis,
+    TimeUnit::Secs,
+    TimeUnit::Minutes,
+    TimeUnit::Hours,
+    TimeUnit::Days,
+];
+
+/// The abbreviated, singular and plural name of each unit, in the order of `UNITS`.
+pub const NAMES: [(&str, &str, &str); 7] = [
+    ("ns", "nanosecond", "nanoseconds"),
+    ("µs", "microsecond", "microseconds"),
+    ("ms", "millisecond", "milliseconds"),
+    ("s", "second", "seconds"),
+    ("m", "minute", "minutes"),
+    ("h", "hour", "hours"),
+    ("d", "day", "days"),
+];
+
+/// The names of picoseconds, which aren't a `TimeUnit` since only
+/// per-iteration times go below nanoseconds.
+pub const PICOS: (&str, &str, &str) = ("ps", "picosecond", "picoseconds");
+
+/// Returns the singular of `names` after exactly `1`, the plural otherwise.
+pub fn name_for(names: (&'static str, &'static str, &'static str), value: &str) -> &'static str {
+    if value == "1" {
+        names.1
+    } else {
+        names.2
+    }
+}
+
+/// Alternative names accepted when parsing.
+const ALIASES: [(&str, TimeUnit); 13] = [
+    ("us", TimeUnit::Micros),
+    ("nsec", TimeUnit::Nanos),
+    ("nsecs", TimeUnit::Nanos),
+    ("usec", TimeUnit::Micros),
+    ("usecs", TimeUnit::Micros),
+    ("msec", TimeUnit::Millis),
+    ("msecs", TimeUnit::Millis),
+    ("sec", TimeUnit::Secs),
+    ("secs", TimeUnit::Secs),
+    ("min", TimeUnit::Minutes),
+    ("mins", TimeUnit::Minutes),
+    ("hr", TimeUnit::Hours),
+    ("hrs", TimeUnit::Hours),
+];
+
+/// Looks up a unit by its abbreviated, singular or plural name,
+/// or by one of the aliases (e.g. `us` for `µs`).
+pub fn from_name(name: &str) -> Option<TimeUnit> {
+    if let Some(&(_, unit)) = ALIASES.iter().find(|&&(alias, _)| alias == name) {
+        return Some(unit);
+    }
+
+    NAMES
+        .iter()
+        .position(|&(short, singular, plural)| name == short || name == singular || name == plural)
+        .map(|i| UNITS[i])
+}
+
+/// A unit of time, ordered from the smallest to the largest.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use floating_duration::{TimeAsFloat, TimeUnit};
+///
+/// let dur = Duration::new(5_400, 0);
+/// assert_eq!(dur.as_fractional(TimeUnit::Hours), 1.5);
+/// assert_eq!(TimeUnit::Minutes.nanos(), 60_000_000_000);
+/// assert_eq!(TimeUnit::Micros.abbreviation(), "µs");
+/// assert_eq!(TimeUnit::Hours.name_for("1"), "hour");
+/// assert_eq!(TimeUnit::Hours.name_for("1.5"), "hours");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimeUnit {
+    /// Nanoseconds.
+    Nanos,
+    /// Microseconds.
+    Micros,
+    /// Milliseconds.
+    Millis,
+    /// Seconds.
+    Secs,
+    /// Minutes (60 seconds).
+    Minutes,
+    /// Hours (60 minutes).
+    Hours,
+    /// Days (24 hours).
+    Days,
+}
+
+impl TimeUnit {
+    /// Returns the number of nanoseconds in one unit.
+    pub fn nanos(self) -> u64 {
+        match self {
+            TimeUnit::Nanos => 1,
+            TimeUnit::Micros => 1_000,
+            TimeUnit::Millis => 1_000_000,
+            TimeUnit::Secs => 1_000_000_000,
+            TimeUnit::Minutes => 60_000_000_000,
+            TimeUnit::Hours => 3_600_000_000_000,
+            TimeUnit::Days => 86_400_000_000_000,
+        }
+    }
+
+    /// Returns the abbreviated name, e.g. `ms`.
+    pub fn abbreviation(self) -> &'static str {
+        NAMES[self as usize].0
+    }
+
+    /// Returns the full singular name, e.g. `millisecond`.
+    pub fn singular_name(self) -> &'static str {
+        NAMES[self as usize].1
+    }
+
+    /// Returns the full plural name, e.g. `milliseconds`.
+    pub fn plural_name(self) -> &'static str {
+        NAMES[self as usize].2
+    }
+
+    /// Returns the full name to use after the formatted number `value`,
+    /// which is singular only for exactly `1`.
+    pub fn name_for(self, value: &str) -> &'static str {
+        name_for(NAMES[self as usize], value)
+    }
+}
@@ -0,0 +1,163 @@
+extern crate floating_duration;
+
+mod common;
+
+use std::time::Duration;
+
+use floating_duration::{TimeAsFloat, TimeUnit};
+
+use common::Rng;
+
+const UNITS: [TimeUnit; 7] = [
+    TimeUnit::Nanos,
+    TimeUnit::Micros,
+    TimeUnit::Millis,
+    TimeUnit::Secs,
+    TimeUnit::Minutes,
+    TimeUnit::Hours,
+    TimeUnit::Days,
+];
+
+fn total_nanos(dur: &Duration) -> u128 {
+    dur.as_secs() as u128 * 1_000_000_000 + dur.subsec_nanos() as u128
+}
+
+/// Compares `num / den` with `k * 2^exp` exactly.
+fn cmp_scaled(num: u128, den: u128, k: u128, exp: i32) -> std::cmp::Ordering {
+    if exp >= 0 {
+        num.cmp(&((den * k) << exp))
+    } else {
+        (num << -exp).cmp(&(den * k))
+    }
+}
+
+/// Checks that `mantissa * 2^exp` (with a `bits`-bit mantissa) is the float
+/// nearest to `num / den`, with ties to even, by comparing the exact quotient
+/// against the midpoints to its neighbours.
+fn assert_correctly_rounded(num: u128, den: u128, mantissa: u128, exp: i32, bits: u32) {
+    use std::cmp::Ordering::*;
+
+    if num == 0 {
+        assert_eq!(mantissa, 0);
+        return;
+    }
+
+    // Midpoints scaled by `2^(exp - 2)`; the gap below a power of two is halved.
+    let lower = if mantissa == 1 << (bits - 1) {
+        4 * mantissa - 1
+    } else {
+        4 * mantissa - 2
+    };
+    let upper = 4 * mantissa + 2;
+    let even = mantissa & 1 == 0;
+
+    match cmp_scaled(num, den, lower, exp - 2) {
+        Greater => {}
+        Equal => assert!(even, "{} / {}: tie not rounded to even", num, den),
+        Less => panic!("{} / {}: result is too large", num, den),
+    }
+    match cmp_scaled(num, den, upper, exp - 2) {
+        Less => {}
+        Equal => assert!(even, "{} / {}: tie not rounded to even", num, den),
+        Greater => panic!("{} / {}: result is too small", num, den),
+    }
+}
+
+fn assert_f64_correctly_rounded(num: u128, den: u128, result: f64) {
+    let bits = result.to_bits();
+    let biased_exp = ((bits >> 52) & 0x7ff) as i32;
+    if result == 0.0 {
+        return assert_correctly_rounded(num, den, 0, 0, 53);
+    }
+    assert!(biased_exp != 0 && biased_exp != 0x7ff, "{} is not normal", result);
+    let mantissa = ((bits & ((1 << 52) - 1)) | (1 << 52)) as u128;
+
+    assert_correctly_rounded(num, den, mantissa, biased_exp - 1075, 53);
+}
+
+fn assert_f32_correctly_rounded(num: u128, den: u128, result: f32) {
+    let bits = result.to_bits();
+    let biased_exp = ((bits >> 23) & 0xff) as i32;
+    if result == 0.0 {
+        return assert_correctly_rounded(num, den, 0, 0, 24);
+    }
+    assert!(biased_exp != 0 && biased_exp != 0xff, "{} is not normal", result);
+    let mantissa = ((bits & ((1 << 23) - 1)) | (1 << 23)) as u128;
+
+    assert_correctly_rounded(num, den, mantissa, biased_exp - 150, 24);
+}
+
+fn check(dur: Duration) {
+    let num = total_nanos(&dur);
+
+    for &unit in &UNITS {
+        let den = unit.nanos() as u128;
+
+        assert_f64_correctly_rounded(num, den, dur.as_fractional(unit));
+        assert_f32_correctly_rounded(num, den, dur.as_fractional_f32(unit));
+    }
+
+    assert_eq!(dur.as_fractional_secs(), dur.as_fractional(TimeUnit::Secs));
+    assert_eq!(dur.as_fractional_millis(), dur.as_fractional(TimeUnit::Millis));
+    assert_eq!(dur.as_fractional_micros(), dur.as_fractional(TimeUnit::Micros));
+    assert_eq!(dur.as_fractional_nanos(), dur.as_fractional(TimeUnit::Nanos));
+    assert_eq!(dur.as_fractional_minutes(), dur.as_fractional(TimeUnit::Minutes));
+    assert_eq!(dur.as_fractional_hours(), dur.as_fractional(TimeUnit::Hours));
+    assert_eq!(dur.as_fractional_days(), dur.as_fractional(TimeUnit::Days));
+    assert_eq!(dur.as_fractional_secs_f32(), dur.as_fractional_f32(TimeUnit::Secs));
+    assert_eq!(dur.as_fractional_millis_f32(), dur.as_fractional_f32(TimeUnit::Millis));
+    assert_eq!(dur.as_fractional_micros_f32(), dur.as_fractional_f32(TimeUnit::Micros));
+}
+
+#[test]
+fn edge_cases() {
+    let mut secs = vec![0, 1, 2, 3, 59, 60, 3_599, 3_600, 86_399, 86_400, 999_999_999];
+    for &p in &[24, 31, 32, 52, 53, 54, 63] {
+        let pow: u64 = 1 << p;
+        secs.extend_from_slice(&[pow - 1, pow, pow + 1]);
+    }
+    secs.extend_from_slice(&[::std::u64::MAX - 1, ::std::u64::MAX]);
+
+    let nanos = [
+        0,
+        1,
+        2,
+        499_999_999,
+        500_000_000,
+        500_000_001,
+        123_456_789,
+        999_999_998,
+        999_999_999,
+    ];
+
+    for &s in &secs {
+        for &n in &nanos {
+            check(Duration::new(s, n));
+        }
+    }
+}
+
+#[test]
+fn pseudo_random() {
+    let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..10_000 {
+        let secs = rng.next_u64() >> (rng.next_u64() % 64);
+        let nanos = (rng.next_u64() % 1_000_000_000) as u32;
+
+        check(Duration::new(secs, nanos));
+    }
+}
+
+#[test]
+fn exact_values() {
+    let dur = Duration::new(4, 123_456_789);
+
+    assert_eq!(dur.as_fractional_nanos(), 4_123_456_789.0);
+    assert_eq!(dur.as_fractional_secs(), 4.123_456_789);
+    assert_eq!(Duration::new(5_400, 0).as_fractional_hours(), 1.5);
+    assert_eq!(Duration::new(0, 1).as_fractional_secs(), 1e-9);
+    assert_eq!(Duration::new(0, 1).as_fractional_millis(), 1e-6);
+    assert_eq!(Duration::new(1, 500_000_000).as_fractional_secs_f32(), 1.5);
+    assert_eq!(Duration::new(0, 1).as_fractional_micros_f32(), 1e-3);
+}
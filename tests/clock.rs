@@ -0,0 +1,42 @@
+extern crate floating_duration;
+
+use std::time::Duration;
+
+use floating_duration::ClockFormat;
+
+#[test]
+fn format() {
+    let fmt = |secs, nanos| format!("{}", ClockFormat::new(Duration::new(secs, nanos)));
+
+    assert_eq!(fmt(0, 0), "00:00.000");
+    assert_eq!(fmt(0, 1), "00:00.000");
+    assert_eq!(fmt(59, 999_500_000), "01:00.000");
+    assert_eq!(fmt(3_599, 999_500_000), "01:00:00.000");
+    assert_eq!(fmt(3_600, 0), "01:00:00.000");
+    assert_eq!(fmt(100 * 3_600, 0), "100:00:00.000");
+}
+
+#[test]
+fn options() {
+    let dur = Duration::new(86_399, 999_999_999);
+    assert_eq!(format!("{}", ClockFormat::new(dur)), "24:00:00.000");
+    assert_eq!(
+        format!("{}", ClockFormat::new(dur).days(true)),
+        "1d 00:00:00.000"
+    );
+    assert_eq!(
+        format!("{:.9}", ClockFormat::new(dur).days(true)),
+        "23:59:59.999999999"
+    );
+    assert_eq!(
+        format!("{:.12}", ClockFormat::new(Duration::new(1, 1))),
+        "00:01.000000001000"
+    );
+
+    let clock = ClockFormat::new(Duration::new(5, 0))
+        .fraction_digits(1)
+        .always_hours(true);
+    assert_eq!(format!("{}", clock), "00:00:05.0");
+    assert_eq!(format!("{:>12}", clock), "  00:00:05.0");
+    assert_eq!(format!("{:*<12.0}", clock), "00:00:05****");
+}
@@ -0,0 +1,30 @@
+//! Helpers shared by the integration tests.
+
+// Not every test uses every helper.
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+/// Returns the duration of `nanos` nanoseconds.
+pub fn ns(nanos: u64) -> Duration {
+    Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32)
+}
+
+/// A xorshift generator, so the pseudo-random tests are reproducible.
+pub struct Rng(u64);
+
+impl Rng {
+    /// Creates a generator from a non-zero `seed`.
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    /// Returns the next pseudo-random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+
+        self.0
+    }
+}
@@ -0,0 +1,80 @@
+extern crate floating_duration;
+
+use std::time::Duration;
+
+use floating_duration::{CompoundFormat, TimeUnit};
+
+#[test]
+fn padding() {
+    let fmt = |secs, nanos| format!("{}", CompoundFormat::new(Duration::new(secs, nanos)));
+
+    assert_eq!(fmt(3_723, 456_000_000), "1h 02m 3.456s");
+    assert_eq!(fmt(3_723, 0), "1h 02m 3s");
+    assert_eq!(fmt(3_600, 0), "1h 00m 0s");
+    assert_eq!(fmt(86_400 + 60, 0), "1d 00h 01m 0s");
+    assert_eq!(fmt(45, 0), "45s");
+    assert_eq!(fmt(0, 0), "0s");
+    assert_eq!(
+        format!("{:.2}", CompoundFormat::new(Duration::new(61, 50_000_000))),
+        "1m 1.05s"
+    );
+
+    let format = CompoundFormat::new(Duration::new(3_605, 0)).skip_zeros(true);
+    assert_eq!(format!("{}", format), "1h 5s");
+
+    let format = CompoundFormat::new(Duration::new(2 * 86_400 + 3 * 3_600, 0)).max_components(2);
+    assert_eq!(format!("{}", format), "2d 03h");
+
+    let format = CompoundFormat::new(Duration::new(1, 2_003_000))
+        .largest_unit(TimeUnit::Secs)
+        .smallest_unit(TimeUnit::Micros);
+    assert_eq!(format!("{}", format), "1s 002ms 3µs");
+    assert_eq!(format!("{:.1}", format), "1s 002ms 3.0µs");
+}
+
+#[test]
+fn alternate() {
+    let fmt = |secs, nanos| format!("{:#}", CompoundFormat::new(Duration::new(secs, nanos)));
+
+    assert_eq!(fmt(3_723, 456_000_000), "1 hour, 2 minutes, 3.456 seconds");
+    assert_eq!(fmt(3_601, 0), "1 hour, 0 minutes, 1 second");
+}
+
+#[test]
+fn components() {
+    let dur = Duration::new(2 * 86_400 + 5 * 60 + 3, 0);
+    let format = CompoundFormat::new(dur).max_components(2);
+    assert_eq!(format!("{}", format), "2d 00h");
+    assert_eq!(format!("{}", format.skip_zeros(true)), "2d 05m");
+    assert_eq!(
+        format!("{}", format.skip_zeros(true).max_components(3)),
+        "2d 05m 3s"
+    );
+    assert_eq!(
+        format!("{:#}", format.skip_zeros(true).max_components(1)),
+        "2 days"
+    );
+
+    let format = CompoundFormat::new(Duration::new(86_400, 0))
+        .skip_zeros(true)
+        .max_components(2);
+    assert_eq!(format!("{}", format), "1d");
+}
+
+#[test]
+fn non_decimal_units() {
+    let format = |secs, nanos| {
+        CompoundFormat::new(Duration::new(secs, nanos))
+            .largest_unit(TimeUnit::Hours)
+            .smallest_unit(TimeUnit::Minutes)
+    };
+
+    assert_eq!(format!("{:.12}", format(1, 0)), "0.016666666667m");
+    assert_eq!(format!("{}", format(3_601, 0)), "1h 0.017m");
+    assert_eq!(format!("{:.2}", format(3_599, 999_999_999)), "1h 0.00m");
+    assert_eq!(format!("{:.20}", format(0, 1)), "0.00000000001666666667m");
+    assert_eq!(
+        format!("{:#.14}", format(7_200, 1)),
+        "2 hours, 0.00000000001667 minutes"
+    );
+}
@@ -0,0 +1,125 @@
+extern crate floating_duration;
+
+mod common;
+
+use std::time::Duration;
+
+use floating_duration::{ExactFormat, RoundingMode};
+
+use common::Rng;
+
+#[test]
+fn decimals() {
+    let dur = Duration::new(4, 123_456_789);
+    let expected = [
+        "4s",
+        "4.1s",
+        "4.12s",
+        "4.123s",
+        "4.1235s",
+        "4.12346s",
+        "4.123457s",
+        "4.1234568s",
+        "4.12345679s",
+        "4.123456789s",
+        "4.1234567890s",
+    ];
+
+    for (decimals, &expected) in expected.iter().enumerate() {
+        assert_eq!(format!("{:.*}", decimals, ExactFormat(dur)), expected);
+    }
+
+    assert_eq!(
+        format!("{}", ExactFormat(Duration::new(0, 0))),
+        "0.000000000s"
+    );
+    assert_eq!(
+        format!("{:.0}", ExactFormat(Duration::new(0, 500_000_000))),
+        "1s"
+    );
+    assert_eq!(
+        format!("{:.0}", ExactFormat(Duration::new(0, 499_999_999))),
+        "0s"
+    );
+    assert_eq!(
+        format!("{:.3}", ExactFormat(Duration::new(9, 999_500_000))),
+        "10.000s"
+    );
+    assert_eq!(
+        format!("{:#.0}", ExactFormat(Duration::new(1, 0))),
+        "1 second"
+    );
+    assert_eq!(
+        format!("{:#.1}", ExactFormat(Duration::new(1, 0))),
+        "1.0 seconds"
+    );
+    assert_eq!(
+        format!("{:>8.2}", ExactFormat(Duration::new(1, 0))),
+        "   1.00s"
+    );
+}
+
+#[test]
+fn rounding() {
+    let fmt = |nanos, mode| {
+        let dur = Duration::new(2, nanos);
+
+        format!("{:.1}", ExactFormat(dur).rounding(mode))
+    };
+
+    assert_eq!(fmt(250_000_000, RoundingMode::HalfUp), "2.3s");
+    assert_eq!(fmt(250_000_000, RoundingMode::HalfEven), "2.2s");
+    assert_eq!(fmt(350_000_000, RoundingMode::HalfEven), "2.4s");
+    assert_eq!(fmt(999_999_999, RoundingMode::Truncate), "2.9s");
+    assert_eq!(fmt(999_999_999, RoundingMode::Floor), "2.9s");
+    assert_eq!(fmt(1, RoundingMode::Ceil), "2.1s");
+
+    let dur = Duration::new(9, 999_999_999);
+    assert_eq!(
+        format!("{:.0}", ExactFormat(dur).rounding(RoundingMode::HalfEven)),
+        "10s"
+    );
+    assert_eq!(
+        format!("{:#.0}", ExactFormat(dur).rounding(RoundingMode::Truncate)),
+        "9 seconds"
+    );
+    assert_eq!(
+        format!("{:.12}", ExactFormat(dur).rounding(RoundingMode::Floor)),
+        "9.999999999000s"
+    );
+    assert_eq!(
+        format!("{:>8.2}", ExactFormat(dur).rounding(RoundingMode::Ceil)),
+        "  10.00s"
+    );
+}
+
+#[test]
+fn large_values() {
+    let dur = Duration::new(u64::max_value(), 999_999_999);
+    assert_eq!(
+        format!("{}", ExactFormat(dur)),
+        "18446744073709551615.999999999s"
+    );
+
+    // A float can't tell these apart.
+    let dur = Duration::new(1 << 53, 1);
+    assert_eq!(
+        format!("{}", ExactFormat(dur)),
+        "9007199254740992.000000001s"
+    );
+}
+
+#[test]
+fn matches_integer_parts() {
+    let mut rng = Rng::new(0xbb67_ae85_84ca_a73b);
+
+    for _ in 0..10_000 {
+        let random = rng.next_u64();
+        let dur = Duration::new(random >> (random % 64), (random % 1_000_000_000) as u32);
+
+        assert_eq!(
+            format!("{}", ExactFormat(dur)),
+            format!("{}.{:09}s", dur.as_secs(), dur.subsec_nanos())
+        );
+    }
+}
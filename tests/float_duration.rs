@@ -0,0 +1,211 @@
+extern crate floating_duration;
+
+mod common;
+
+use std::time::Duration;
+
+use floating_duration::{
+    FloatDuration, FromFloatError, RoundingMode, SignedDuration, TimeAsFloat, TimeFormat,
+    TimeFormatStyle, TimeUnit,
+};
+
+use common::{ns, Rng};
+
+#[test]
+fn arithmetic() {
+    let a = FloatDuration(1.5);
+    let b = FloatDuration(0.5);
+
+    assert_eq!(a + b, FloatDuration(2.0));
+    assert_eq!(a - b, FloatDuration(1.0));
+    assert_eq!(b - a, FloatDuration(-1.0));
+    assert_eq!(a * 2.0, FloatDuration(3.0));
+    assert_eq!(a / 2.0, FloatDuration(0.75));
+    assert_eq!(a / b, 3.0);
+    assert_eq!(-a, FloatDuration(-1.5));
+
+    let values = [a, b, FloatDuration(1.0)];
+    assert_eq!(values.iter().sum::<FloatDuration>(), FloatDuration(3.0));
+    assert_eq!(
+        values.iter().cloned().sum::<FloatDuration>(),
+        FloatDuration(3.0)
+    );
+    assert_eq!(
+        Vec::<FloatDuration>::new()
+            .into_iter()
+            .sum::<FloatDuration>(),
+        FloatDuration(0.0)
+    );
+
+    assert!(b < a);
+    assert!(-a < b);
+    assert_eq!(FloatDuration(std::f64::NAN).partial_cmp(&a), None);
+}
+
+#[test]
+fn conversions() {
+    let dur = Duration::new(4, 500_000_000);
+    assert_eq!(FloatDuration::from(dur), FloatDuration(4.5));
+    assert_eq!(FloatDuration(4.5).to_duration(), Ok(dur));
+    assert_eq!(
+        FloatDuration(1e-9 * 0.4).to_duration(),
+        Ok(Duration::new(0, 0))
+    );
+    assert_eq!(
+        FloatDuration(-1.0).to_duration(),
+        Err(FromFloatError::Negative)
+    );
+    assert_eq!(
+        FloatDuration(std::f64::NAN).to_duration(),
+        Err(FromFloatError::NotANumber)
+    );
+
+    let signed = SignedDuration::new(true, dur);
+    assert_eq!(FloatDuration::from(signed), FloatDuration(-4.5));
+
+    assert_eq!(
+        FloatDuration::new(250.0, TimeUnit::Millis),
+        FloatDuration(0.25)
+    );
+    assert_eq!(
+        FloatDuration::new(2.0, TimeUnit::Hours),
+        FloatDuration(7_200.0)
+    );
+
+    let dur = FloatDuration(5_400.0);
+    assert_eq!(dur.as_fractional_hours(), 1.5);
+    assert_eq!(dur.as_fractional(TimeUnit::Minutes), 90.0);
+    assert_eq!(dur.as_fractional_millis(), 5_400_000.0);
+    assert_eq!(FloatDuration(-0.5).as_fractional_nanos(), -500_000_000.0);
+}
+
+#[test]
+fn format() {
+    let fmt = |secs| format!("{}", FloatDuration(secs));
+
+    assert_eq!(fmt(12.5), "12.5s");
+    assert_eq!(fmt(1.0), "1s");
+    assert_eq!(fmt(0.001_5), "1.5ms");
+    assert_eq!(fmt(0.000_461_93), "461.93µs");
+    assert_eq!(fmt(0.000_999_999_9), "1ms");
+    assert_eq!(fmt(1.27e-9), "1.27ns");
+    assert_eq!(fmt(0.35e-9), "0.35ns");
+    assert_eq!(fmt(1e-13), "0ns");
+    assert_eq!(fmt(0.0), "0ns");
+    assert_eq!(fmt(-1e-13), "0ns");
+    assert_eq!(fmt(-0.002), "-2ms");
+    assert_eq!(fmt(-0.35e-9), "-0.35ns");
+    assert_eq!(fmt(std::f64::NAN), "NaN");
+    assert_eq!(fmt(std::f64::INFINITY), "inf");
+    assert_eq!(fmt(std::f64::NEG_INFINITY), "-inf");
+
+    let dur = FloatDuration(0.35e-9);
+    assert_eq!(format!("{:.1}", dur), "0.4ns");
+    assert_eq!(format!("{:.4}", dur), "0.3500ns");
+    assert_eq!(format!("{:#}", dur), "0.35 nanoseconds");
+    assert_eq!(format!("{:#}", FloatDuration(1.0)), "1 second");
+    assert_eq!(format!("[{:>+9}]", dur), "[  +0.35ns]");
+    assert_eq!(format!("[{:>9}]", -dur), "[  -0.35ns]");
+
+    assert_eq!(fmt(1e-20), "0ns");
+    assert_eq!(
+        format!("{:.22}", FloatDuration(1.25e-29)),
+        "0.0000000000000000000125ns"
+    );
+    assert_eq!(
+        format!("{:.22}", FloatDuration(1.234_567_890_123_456_7e-27)),
+        "0.0000000000000000012346ns"
+    );
+    assert_eq!(fmt(1e300), "1e300s");
+    assert_eq!(fmt(-1e300), "-1e300s");
+    assert_eq!(format!("{:#}", FloatDuration(1e300)), "1e300 seconds");
+}
+
+#[test]
+fn style() {
+    let fmt = |secs, style| format!("{}", TimeFormat::with_style(FloatDuration(secs), style));
+
+    let style = TimeFormatStyle::new().decimals(0);
+    assert_eq!(fmt(2.5e-9, style), "3ns");
+    assert_eq!(fmt(0.35e-9, style), "0ns");
+    assert_eq!(fmt(-2.5e-9, style.rounding(RoundingMode::Floor)), "-3ns");
+    assert_eq!(fmt(-2.5e-9, style.rounding(RoundingMode::Ceil)), "-2ns");
+    assert_eq!(fmt(2.5e-9, style.rounding(RoundingMode::HalfEven)), "2ns");
+    assert_eq!(fmt(-0.35e-9, style.rounding(RoundingMode::Ceil)), "0ns");
+
+    let style = TimeFormatStyle::new().max_unit(TimeUnit::Days).space(true);
+    assert_eq!(fmt(5_400.0, style), "1.5 h");
+    assert_eq!(fmt(1.234_567_890_123_456_7e-27, style), "0 ns");
+    assert_eq!(
+        fmt(1.234_567_890_123_456_7e-27, style.significant_figures(2)),
+        "0.0000000000000000012 ns"
+    );
+
+    let style = TimeFormatStyle::new().significant_figures(3);
+    assert_eq!(fmt(1.234_5e-10, style), "0.123ns");
+    assert_eq!(fmt(0.016_345_5, style), "16.3ms");
+
+    let style = TimeFormatStyle::new().unit(TimeUnit::Micros).decimals(6);
+    assert_eq!(fmt(1.5e-10, style), "0.00015µs");
+
+    let style = TimeFormatStyle::new().long_names(true);
+    assert_eq!(fmt(-1e300, style), "-1e300 seconds");
+}
+
+#[test]
+fn matches_duration() {
+    // The tie which `f64` arithmetic gets wrong.
+    let dur = Duration::new(0, 16_345_500);
+    assert_eq!(format!("{}", FloatDuration::from(dur)), "16.346ms");
+
+    let styles = [
+        TimeFormatStyle::new(),
+        TimeFormatStyle::new().decimals(0).max_unit(TimeUnit::Days),
+        TimeFormatStyle::new()
+            .decimals(1)
+            .rounding(RoundingMode::HalfEven),
+        TimeFormatStyle::new().rounding(RoundingMode::Ceil),
+        TimeFormatStyle::new().significant_figures(4),
+        TimeFormatStyle::new()
+            .strip_zeros(false)
+            .unit(TimeUnit::Millis),
+    ];
+
+    let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+    for i in 0..50_000 {
+        let random = rng.next_u64();
+        // Durations with up to 15 significant digits survive the `f64`.
+        let nanos = match i % 3 {
+            0 => random % 1_000_000_000,
+            1 => (random % 1_000_000) * 1_000,
+            _ => random % 1_000_000_000_000_000,
+        };
+        let dur = ns(nanos);
+        let float = FloatDuration::from(dur);
+
+        for &style in &styles {
+            let expected = format!("{}", TimeFormat::with_style(dur, style));
+            assert_eq!(
+                format!("{}", TimeFormat::with_style(float, style)),
+                expected,
+                "{:?}",
+                dur
+            );
+            assert_eq!(
+                format!("{}", TimeFormat::with_style(-float, style)),
+                format!(
+                    "{}",
+                    TimeFormat::with_style(-SignedDuration::from(dur), style)
+                ),
+                "{:?}",
+                dur
+            );
+        }
+        assert_eq!(
+            format!("{:.2}", float),
+            format!("{:.2}", TimeFormat(dur)),
+            "{:?}",
+            dur
+        );
+    }
+}
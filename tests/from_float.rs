@@ -0,0 +1,127 @@
+extern crate floating_duration;
+
+mod common;
+
+use std::f64;
+use std::time::Duration;
+
+use floating_duration::{DurationFromFloat, FromFloatError};
+
+use common::{ns, Rng};
+
+#[test]
+fn errors() {
+    assert_eq!(
+        Duration::from_fractional_secs(f64::NAN),
+        Err(FromFloatError::NotANumber)
+    );
+    assert_eq!(
+        Duration::from_fractional_millis(f64::INFINITY),
+        Err(FromFloatError::Infinite)
+    );
+    assert_eq!(
+        Duration::from_fractional_micros(f64::NEG_INFINITY),
+        Err(FromFloatError::Infinite)
+    );
+    assert_eq!(
+        Duration::from_fractional_nanos(-1.0),
+        Err(FromFloatError::Negative)
+    );
+    assert_eq!(
+        Duration::from_fractional_secs(-f64::MIN_POSITIVE),
+        Err(FromFloatError::Negative)
+    );
+    assert_eq!(
+        Duration::from_fractional_secs(18_446_744_073_709_551_616.0),
+        Err(FromFloatError::OutOfRange)
+    );
+    assert_eq!(
+        Duration::from_fractional_millis(f64::MAX),
+        Err(FromFloatError::OutOfRange)
+    );
+
+    assert_eq!(
+        FromFloatError::OutOfRange.to_string(),
+        "value is too large for a duration"
+    );
+}
+
+#[test]
+fn ties_to_even() {
+    let nanos = |value| Duration::from_fractional_nanos(value).unwrap();
+
+    assert_eq!(nanos(0.5), Duration::new(0, 0));
+    assert_eq!(nanos(1.5), Duration::new(0, 2));
+    assert_eq!(nanos(2.5), Duration::new(0, 2));
+    assert_eq!(nanos(2.500_000_000_000_001), Duration::new(0, 3));
+    assert_eq!(nanos(3.5), Duration::new(0, 4));
+    assert_eq!(nanos(0.499_999_999_999_999_94), Duration::new(0, 0));
+
+    // 2^-31 seconds is 0.4656612873077392578125ns.
+    let secs = 1.0 / 2_147_483_648.0;
+    assert_eq!(
+        Duration::from_fractional_secs(secs),
+        Ok(Duration::new(0, 0))
+    );
+    assert_eq!(
+        Duration::from_fractional_secs(secs * 5.0),
+        Ok(Duration::new(0, 2))
+    );
+}
+
+#[test]
+fn small_values() {
+    assert_eq!(Duration::from_fractional_secs(0.0), Ok(Duration::new(0, 0)));
+    assert_eq!(
+        Duration::from_fractional_secs(-0.0),
+        Ok(Duration::new(0, 0))
+    );
+
+    // Subnormals are far below half a nanosecond.
+    let subnormal = f64::MIN_POSITIVE / 4.0;
+    assert_eq!(
+        Duration::from_fractional_secs(subnormal),
+        Ok(Duration::new(0, 0))
+    );
+    assert_eq!(
+        Duration::from_fractional_nanos(5e-324),
+        Ok(Duration::new(0, 0))
+    );
+    assert_eq!(
+        Duration::from_fractional_nanos(f64::MIN_POSITIVE),
+        Ok(Duration::new(0, 0))
+    );
+}
+
+#[test]
+fn largest_values() {
+    // The largest `f64` below 2^64 is 2^64 - 2048.
+    let secs = 18_446_744_073_709_549_568.0;
+    assert_eq!(
+        Duration::from_fractional_secs(secs),
+        Ok(Duration::new(u64::max_value() - 2_047, 0))
+    );
+
+    // 2^73 milliseconds.
+    assert_eq!(
+        Duration::from_fractional_millis(9_444_732_965_739_290_427_392.0),
+        Ok(Duration::new(9_444_732_965_739_290_427, 392_000_000))
+    );
+}
+
+#[test]
+fn round_trip() {
+    let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..10_000 {
+        let random = rng.next_u64();
+        // Below 2^53 nanoseconds, every duration is an exact `f64`.
+        let nanos = random >> 11;
+        let dur = ns(nanos);
+
+        assert_eq!(Duration::from_fractional_nanos(nanos as f64), Ok(dur));
+        assert_eq!(
+            Duration::from_fractional_secs(nanos as f64),
+            Ok(Duration::new(nanos, 0))
+        );
+    }
+}
@@ -0,0 +1,99 @@
+extern crate floating_duration;
+
+mod common;
+
+use std::time::Duration;
+
+use floating_duration::{format_all, GroupBasis, TimeFormatGroup, TimeFormatStyle, TimeUnit};
+
+use common::ns;
+
+fn column(durations: &[Duration], width: usize) -> Vec<String> {
+    format_all(durations)
+        .iter()
+        .map(|f| format!("{:1$}", f, width))
+        .collect()
+}
+
+#[test]
+fn shared_unit() {
+    let durations = [ns(980_000), ns(1_200_000), ns(15_000_000), ns(2_000)];
+    // The lower median is 980µs.
+    assert_eq!(
+        column(&durations, 8),
+        ["   980µs", "  1200µs", " 15000µs", "     2µs"]
+    );
+
+    let group = TimeFormatGroup::with_style(&durations, TimeFormatStyle::new(), GroupBasis::Max);
+    assert_eq!(group.unit(), TimeUnit::Millis);
+    assert_eq!(group.decimals(), 3);
+    assert_eq!(format!("{}", group.format(durations[0])), "0.980ms");
+
+    let durations = [ns(5), ns(12), ns(7_000)];
+    assert_eq!(column(&durations, 0), ["5ns", "12ns", "7000ns"]);
+
+    let durations = [ns(1), ns(1_000), ns(1_000_000_000)];
+    let group = TimeFormatGroup::new(&durations);
+    assert_eq!(group.unit(), TimeUnit::Micros);
+    assert_eq!(group.decimals(), 3);
+    assert_eq!(format!("{}", group.format(durations[0])), "0.001µs");
+    assert_eq!(format!("{}", group.format(durations[2])), "1000000.000µs");
+
+    let empty: [Duration; 0] = [];
+    let durations = [ns(999_600), ns(999_747), ns(1_000)];
+    assert_eq!(TimeFormatGroup::new(&durations).unit(), TimeUnit::Micros);
+
+    assert!(format_all(&empty).is_empty());
+    assert_eq!(TimeFormatGroup::new(&empty).unit(), TimeUnit::Nanos);
+}
+
+#[test]
+fn alignment() {
+    let durations = [ns(1_500_000), ns(250_000_000), ns(12_345_000)];
+    let group = TimeFormatGroup::new(durations.iter());
+
+    let rows: Vec<_> = durations
+        .iter()
+        .map(|&d| format!("|{:>10}|", group.format(d)))
+        .collect();
+    assert_eq!(rows, ["|   1.500ms|", "| 250.000ms|", "|  12.345ms|"]);
+
+    let rows: Vec<_> = durations
+        .iter()
+        .map(|&d| format!("|{:10.1}|", group.format(d)))
+        .collect();
+    assert_eq!(rows, ["|     1.5ms|", "|   250.0ms|", "|    12.3ms|"]);
+
+    assert_eq!(
+        format!("|{:<10}|", group.format(durations[0])),
+        "|1.500ms   |"
+    );
+    assert_eq!(
+        format!("{:#}", group.format(durations[0])),
+        "1.500 milliseconds"
+    );
+
+    let durations = [ns(1_000_000_000), ns(2_000_000_000), ns(12_000_000_000)];
+    let style = TimeFormatStyle::new().decimals(0);
+    let group = TimeFormatGroup::with_style(&durations, style, GroupBasis::Max);
+    let rows: Vec<_> = durations
+        .iter()
+        .map(|&d| format!("[{:>#12}]", group.format(d)))
+        .collect();
+    assert_eq!(rows, ["[   1 second ]", "[   2 seconds]", "[  12 seconds]"]);
+
+    let long = TimeFormatGroup::with_style(&durations, style.long_names(true), GroupBasis::Max);
+    assert_eq!(
+        format!("[{:>12}]", long.format(durations[0])),
+        "[   1 second ]"
+    );
+    assert_eq!(
+        format!("[{:*<#12}]", group.format(durations[0])),
+        "[1 second****]"
+    );
+    assert_eq!(format!("{:#}", group.format(durations[0])), "1 second");
+    assert_eq!(
+        format!("[{:>#12.1}]", group.format(durations[0])),
+        "[ 1.0 seconds]"
+    );
+}
@@ -0,0 +1,132 @@
+extern crate floating_duration;
+
+mod common;
+
+use std::time::Duration;
+
+use floating_duration::{parse_iso8601, Iso8601Format, Iso8601Mode, ParseErrorKind, RoundingMode};
+
+use common::Rng;
+
+fn strict(s: &str) -> Result<Duration, (ParseErrorKind, usize)> {
+    parse_iso8601(s, Iso8601Mode::Strict).map_err(|e| (e.kind(), e.offset()))
+}
+
+#[test]
+fn format() {
+    let fmt = |secs, nanos| format!("{}", Iso8601Format(Duration::new(secs, nanos)));
+
+    assert_eq!(fmt(0, 0), "PT0S");
+    assert_eq!(fmt(0, 1), "PT0.000000001S");
+    assert_eq!(fmt(0, 500_000_000), "PT0.5S");
+    assert_eq!(fmt(60, 0), "PT1M");
+    assert_eq!(fmt(3_600, 0), "PT1H");
+    assert_eq!(fmt(3_601, 0), "PT1H1S");
+    assert_eq!(fmt(93_784, 0), "PT26H3M4S");
+
+    let dur = Duration::new(59, 999_600_000);
+    assert_eq!(format!("{:.3}", Iso8601Format(dur)), "PT1M");
+    assert_eq!(format!("{:.4}", Iso8601Format(dur)), "PT59.9996S");
+    assert_eq!(format!("{:.6}", Iso8601Format(dur)), "PT59.999600S");
+    assert_eq!(
+        format!("{:>10}", Iso8601Format(Duration::new(1, 0))),
+        "      PT1S"
+    );
+}
+
+#[test]
+fn rounding() {
+    let fmt = |nanos, mode| {
+        let dur = Duration::new(1, nanos);
+
+        format!("{:.1}", Iso8601Format(dur).rounding(mode))
+    };
+
+    assert_eq!(fmt(250_000_000, RoundingMode::HalfUp), "PT1.3S");
+    assert_eq!(fmt(250_000_000, RoundingMode::HalfEven), "PT1.2S");
+    assert_eq!(fmt(999_999_999, RoundingMode::Truncate), "PT1.9S");
+    assert_eq!(fmt(1, RoundingMode::Ceil), "PT1.1S");
+    assert_eq!(fmt(50_000_000, RoundingMode::Floor), "PT1.0S");
+
+    let dur = Duration::new(3_599, 999_999_999);
+    assert_eq!(
+        format!("{}", Iso8601Format(dur).rounding(RoundingMode::HalfUp)),
+        "PT59M59.999999999S"
+    );
+    assert_eq!(
+        format!("{:.0}", Iso8601Format(dur).rounding(RoundingMode::HalfUp)),
+        "PT1H"
+    );
+    assert_eq!(
+        format!("{:>10.0}", Iso8601Format(dur).rounding(RoundingMode::Floor)),
+        "  PT59M59S"
+    );
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        strict("PT1H2M3.456S"),
+        Ok(Duration::new(3_723, 456_000_000))
+    );
+    assert_eq!(strict("PT3,5S"), Ok(Duration::new(3, 500_000_000)));
+    assert_eq!(strict("P1DT12H"), Ok(Duration::new(129_600, 0)));
+    assert_eq!(strict("P2W"), Ok(Duration::new(1_209_600, 0)));
+    assert_eq!(strict("P1W1D"), Ok(Duration::new(691_200, 0)));
+    assert_eq!(strict("P0.5D"), Ok(Duration::new(43_200, 0)));
+    assert_eq!(strict("PT0.000000001S"), Ok(Duration::new(0, 1)));
+    assert_eq!(strict("PT0.0000000005S"), Ok(Duration::new(0, 1)));
+    assert_eq!(strict("+PT1S"), Ok(Duration::new(1, 0)));
+    assert_eq!(strict("-PT0S"), Ok(Duration::new(0, 0)));
+
+    assert_eq!(
+        parse_iso8601("P1Y2M", Iso8601Mode::Lenient),
+        Ok(Duration::new(425 * 86_400, 0))
+    );
+}
+
+#[test]
+fn errors() {
+    assert_eq!(strict(""), Err((ParseErrorKind::Empty, 0)));
+    assert_eq!(strict("1H"), Err((ParseErrorKind::UnexpectedCharacter, 0)));
+    assert_eq!(strict("P"), Err((ParseErrorKind::InvalidNumber, 1)));
+    assert_eq!(strict("PT"), Err((ParseErrorKind::InvalidNumber, 2)));
+    assert_eq!(strict("P1DT"), Err((ParseErrorKind::InvalidNumber, 4)));
+    assert_eq!(strict("PT1"), Err((ParseErrorKind::MissingUnit, 3)));
+    assert_eq!(strict("P1H"), Err((ParseErrorKind::UnexpectedCharacter, 2)));
+    assert_eq!(
+        strict("PT1S2M"),
+        Err((ParseErrorKind::UnexpectedCharacter, 5))
+    );
+    assert_eq!(
+        strict("PT1M1M"),
+        Err((ParseErrorKind::UnexpectedCharacter, 5))
+    );
+    assert_eq!(strict("P1Y"), Err((ParseErrorKind::CalendarUnit, 2)));
+    assert_eq!(strict("P1M"), Err((ParseErrorKind::CalendarUnit, 2)));
+    assert_eq!(
+        strict("pt1s"),
+        Err((ParseErrorKind::UnexpectedCharacter, 0))
+    );
+    assert_eq!(strict("-PT1S"), Err((ParseErrorKind::Negative, 0)));
+    assert_eq!(
+        strict("P99999999999999999999999D"),
+        Err((ParseErrorKind::OutOfRange, 1))
+    );
+}
+
+#[test]
+fn round_trip() {
+    let mut rng = Rng::new(0x853c_49e6_748f_ea9b);
+
+    for _ in 0..10_000 {
+        let dur = Duration::new(
+            rng.next_u64() >> (rng.next_u64() % 64),
+            (rng.next_u64() % 1_000_000_000) as u32,
+        );
+        let formatted = format!("{}", Iso8601Format(dur));
+
+        let Iso8601Format(parsed) = formatted.parse().unwrap();
+        assert_eq!(parsed, dur, "{}", formatted);
+    }
+}
@@ -0,0 +1,82 @@
+extern crate floating_duration;
+
+use std::time::Duration;
+
+use floating_duration::{parse_duration, CompoundFormat, ParseErrorKind};
+
+fn err(s: &str) -> (ParseErrorKind, usize) {
+    let err = parse_duration(s).unwrap_err();
+
+    (err.kind(), err.offset())
+}
+
+#[test]
+fn components() {
+    assert_eq!(parse_duration("1.5s"), Ok(Duration::new(1, 500_000_000)));
+    assert_eq!(parse_duration(".5s"), Ok(Duration::new(0, 500_000_000)));
+    assert_eq!(
+        parse_duration("1d2h3m4s5ms6us7ns"),
+        Ok(Duration::new(93_784, 5_006_007))
+    );
+    assert_eq!(
+        parse_duration(" 1 hour,  2 minutes , 3.5 seconds "),
+        Ok(Duration::new(3_723, 500_000_000))
+    );
+    assert_eq!(parse_duration("1 day 1 day"), Ok(Duration::new(172_800, 0)));
+    assert_eq!(parse_duration("10 mins 5 secs"), Ok(Duration::new(605, 0)));
+    assert_eq!(parse_duration("-0s"), Ok(Duration::new(0, 0)));
+}
+
+#[test]
+fn exact_rounding() {
+    // 1/3 of a nanosecond three times is rounded once, after summing.
+    let third = "0.333333333333333333333333334ns";
+    let input = format!("{} {} {}", third, third, third);
+    assert_eq!(parse_duration(&input), Ok(Duration::new(0, 1)));
+
+    assert_eq!(parse_duration("0.5ns"), Ok(Duration::new(0, 1)));
+    assert_eq!(parse_duration("0.4999ns"), Ok(Duration::new(0, 0)));
+    assert_eq!(parse_duration("0.0000000005s"), Ok(Duration::new(0, 1)));
+    assert_eq!(
+        parse_duration("1.000000000001h"),
+        Ok(Duration::new(3_600, 4))
+    );
+    assert_eq!(parse_duration("0.1m"), Ok(Duration::new(6, 0)));
+}
+
+#[test]
+fn compound_format_round_trip() {
+    for &secs in &[0, 1, 59, 61, 3_599, 3_723, 86_399, 90_061, 1_000_000] {
+        for &nanos in &[0, 1_000_000, 456_000_000] {
+            let dur = Duration::new(secs, nanos);
+
+            let short = format!("{}", CompoundFormat::new(dur));
+            assert_eq!(parse_duration(&short), Ok(dur), "{}", short);
+
+            let long = format!("{:#}", CompoundFormat::new(dur));
+            assert_eq!(parse_duration(&long), Ok(dur), "{}", long);
+        }
+    }
+}
+
+#[test]
+fn errors() {
+    assert_eq!(err(""), (ParseErrorKind::Empty, 0));
+    assert_eq!(err("   "), (ParseErrorKind::Empty, 3));
+    assert_eq!(err("s"), (ParseErrorKind::InvalidNumber, 0));
+    assert_eq!(err("1h 30"), (ParseErrorKind::MissingUnit, 5));
+    assert_eq!(err("5."), (ParseErrorKind::MissingUnit, 2));
+    assert_eq!(err("1h, "), (ParseErrorKind::InvalidNumber, 4));
+    assert_eq!(err("3 weeks"), (ParseErrorKind::UnknownUnit, 2));
+    assert_eq!(err("1h; 2m"), (ParseErrorKind::UnexpectedCharacter, 2));
+    assert_eq!(err("- 1s"), (ParseErrorKind::Negative, 0));
+    assert_eq!(err("1s -1s"), (ParseErrorKind::UnexpectedCharacter, 3));
+    assert_eq!(
+        err("18446744073709551615s 1s"),
+        (ParseErrorKind::OutOfRange, 22)
+    );
+    assert_eq!(
+        err("999999999999999999999999999999999999999999d"),
+        (ParseErrorKind::OutOfRange, 0)
+    );
+}
@@ -0,0 +1,123 @@
+extern crate floating_duration;
+
+mod common;
+
+use std::time::Duration;
+
+use floating_duration::{RoundingMode, TimeFormat};
+
+use common::Rng;
+
+fn fmt(secs: u64, nanos: u32, iterations: u64) -> String {
+    format!(
+        "{}",
+        TimeFormat::per_iteration(Duration::new(secs, nanos), iterations)
+    )
+}
+
+#[test]
+fn units() {
+    assert_eq!(fmt(0, 350_000_000, 1_000_000_000), "350ps");
+    assert_eq!(fmt(1, 270_000_000, 1_000_000_000), "1.27ns");
+    assert_eq!(fmt(0, 1, 1_000_000), "0.001ps");
+    assert_eq!(fmt(0, 1, 10_000_000), "0ps");
+    assert_eq!(fmt(0, 0, 5), "0ns");
+    assert_eq!(fmt(0, 999_999_600, 1_000_000_000), "1ns");
+    assert_eq!(fmt(0, 999_499_000, 1_000_000_000), "999.499ps");
+    assert_eq!(fmt(0, 999_600_000, 1_000), "999.6µs");
+    assert_eq!(fmt(0, 999_999_600, 1_000), "1ms");
+    assert_eq!(fmt(3, 0, 2), "1.5s");
+    assert_eq!(fmt(10_000, 0, 1), "10000s");
+    assert_eq!(fmt(1, 0, 3), "333.333ms");
+    assert_eq!(fmt(2, 0, 3), "666.667ms");
+
+    let max = Duration::new(u64::max_value(), 999_999_999);
+    assert_eq!(
+        format!("{}", TimeFormat::per_iteration(max, u64::max_value())),
+        "1s"
+    );
+    assert_eq!(
+        format!("{}", TimeFormat::per_iteration(max, 1)),
+        format!("{}", TimeFormat(max))
+    );
+}
+
+#[test]
+fn flags() {
+    let dur = Duration::new(1, 0);
+    let per = |iterations| TimeFormat::per_iteration(dur, iterations);
+
+    assert_eq!(format!("{:.2}", per(3_000_000_000)), "333.33ps");
+    assert_eq!(format!("{:.0}", per(3_000_000_000)), "333ps");
+    assert_eq!(format!("{:.5}", per(1_000_000_000_000)), "1.00000ps");
+    assert_eq!(
+        format!("{:.30}", per(3)),
+        "333.333333333333333333333333333333ms"
+    );
+    assert_eq!(
+        format!("{:.40}", per(7)),
+        "142.8571428571428571428571428571428571428571ms"
+    );
+    assert_eq!(
+        format!("{:.25}", TimeFormat::per_iteration(Duration::new(2, 0), 3)),
+        "666.6666666666666666666666667ms"
+    );
+    assert_eq!(format!("{:#}", per(1_000_000_000_000)), "1 picosecond");
+    assert_eq!(format!("{:#}", per(2_000_000_000_000)), "0.5 picoseconds");
+    assert_eq!(format!("{:#}", per(1_000)), "1 millisecond");
+    assert_eq!(format!("[{:>+8}]", per(4_000_000_000)), "[  +250ps]");
+    assert_eq!(format!("[{:^9}]", per(4_000_000_000)), "[  250ps  ]");
+}
+
+#[test]
+fn rounding() {
+    let per = |nanos, mode| {
+        let fmt = TimeFormat::per_iteration(Duration::new(0, nanos), 1_000_000);
+
+        format!("{:.1}", fmt.rounding(mode))
+    };
+
+    assert_eq!(per(2_250, RoundingMode::HalfUp), "2.3ps");
+    assert_eq!(per(2_250, RoundingMode::HalfEven), "2.2ps");
+    assert_eq!(per(2_350, RoundingMode::HalfEven), "2.4ps");
+    assert_eq!(per(2_299, RoundingMode::Truncate), "2.2ps");
+    assert_eq!(per(2_299, RoundingMode::Floor), "2.2ps");
+    assert_eq!(per(2_201, RoundingMode::Ceil), "2.3ps");
+
+    // Rounding up may still carry over into the next unit.
+    assert_eq!(per(999_999, RoundingMode::Ceil), "1.0ns");
+    assert_eq!(per(999_999, RoundingMode::Truncate), "999.9ps");
+
+    let fmt = TimeFormat::per_iteration(Duration::new(1, 0), 7).rounding(RoundingMode::Floor);
+    assert_eq!(format!("{:#.3}", fmt), "142.857 milliseconds");
+}
+
+#[test]
+fn matches_time_format() {
+    assert_eq!(
+        format!("{:#}", TimeFormat::per_iteration(Duration::new(0, 0), 1)),
+        format!("{:#}", TimeFormat(Duration::new(0, 0))),
+    );
+
+    // With one iteration, the output is the same as for the duration itself.
+    let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..1_000 {
+        let random = rng.next_u64();
+        let dur = Duration::new(random % 100, ((random >> 32) as u32 % 1_000_000_000) | 1);
+
+        assert_eq!(
+            format!("{}", TimeFormat::per_iteration(dur, 1)),
+            format!("{}", TimeFormat(dur)),
+        );
+        assert_eq!(
+            format!("{:.6}", TimeFormat::per_iteration(dur * 1_000, 1_000)),
+            format!("{:.6}", TimeFormat(dur)),
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn zero_iterations() {
+    TimeFormat::per_iteration(Duration::new(1, 0), 0);
+}
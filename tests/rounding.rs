@@ -0,0 +1,152 @@
+extern crate floating_duration;
+
+mod common;
+
+use std::time::Duration;
+
+use floating_duration::{
+    ClockFormat, CompoundFormat, DurationTemplate, RoundingMode, TimeFormat, TimeFormatStyle,
+    TimeUnit,
+};
+
+use common::Rng;
+
+const MODES: [RoundingMode; 5] = [
+    RoundingMode::HalfUp,
+    RoundingMode::HalfEven,
+    RoundingMode::Truncate,
+    RoundingMode::Floor,
+    RoundingMode::Ceil,
+];
+
+fn fixed(nanos: u64, decimals: usize, mode: RoundingMode) -> String {
+    let style = TimeFormatStyle::new()
+        .unit(TimeUnit::Micros)
+        .decimals(decimals)
+        .rounding(mode);
+
+    format!(
+        "{}",
+        TimeFormat::with_style(Duration::new(0, nanos as u32), style)
+    )
+}
+
+/// Rounds `num / den` by checking the candidates around it.
+fn reference(num: u64, den: u64, mode: RoundingMode) -> u64 {
+    let (down, rem) = (num / den, num % den);
+    let up = if rem == 0 { down } else { down + 1 };
+
+    match mode {
+        RoundingMode::Truncate | RoundingMode::Floor => down,
+        RoundingMode::Ceil => up,
+        RoundingMode::HalfUp => {
+            if 2 * rem >= den {
+                up
+            } else {
+                down
+            }
+        }
+        RoundingMode::HalfEven => {
+            if 2 * rem > den || (2 * rem == den && down % 2 == 1) {
+                up
+            } else {
+                down
+            }
+        }
+    }
+}
+
+#[test]
+fn modes() {
+    let cases = [
+        (2_500, ["3µs", "2µs", "2µs", "2µs", "3µs"]),
+        (3_500, ["4µs", "4µs", "3µs", "3µs", "4µs"]),
+        (3_499, ["3µs", "3µs", "3µs", "3µs", "4µs"]),
+        (3_000, ["3µs", "3µs", "3µs", "3µs", "3µs"]),
+        (0, ["0µs", "0µs", "0µs", "0µs", "0µs"]),
+    ];
+
+    for &(nanos, expected) in &cases {
+        for (&mode, &expected) in MODES.iter().zip(expected.iter()) {
+            assert_eq!(fixed(nanos, 0, mode), expected, "{} {:?}", nanos, mode);
+        }
+    }
+}
+
+#[test]
+fn exact_reference() {
+    let mut rng = Rng::new(0x6a09_e667_f3bc_c909);
+
+    for _ in 0..10_000 {
+        let random = rng.next_u64();
+        let nanos = (random % 1_000_000_000) >> (random % 24);
+        let decimals = (random >> 40) as usize % 4;
+
+        for &mode in &MODES {
+            let den = 10u64.pow(3 - decimals as u32);
+            let scaled = reference(nanos, den, mode);
+            let pow = 10u64.pow(decimals as u32);
+            let mut expected = (scaled / pow).to_string();
+            if decimals > 0 {
+                let mut fraction = format!("{:01$}", scaled % pow, decimals);
+                while fraction.ends_with('0') {
+                    fraction.pop();
+                }
+                if !fraction.is_empty() {
+                    expected = format!("{}.{}", expected, fraction);
+                }
+            }
+            expected.push_str("µs");
+
+            assert_eq!(fixed(nanos, decimals, mode), expected, "{:?}", mode);
+        }
+    }
+}
+
+#[test]
+fn unit_selection() {
+    let fmt = |nanos, mode| {
+        let style = TimeFormatStyle::new().decimals(0).rounding(mode);
+
+        format!("{}", TimeFormat::with_style(Duration::new(0, nanos), style))
+    };
+
+    assert_eq!(fmt(2_000_001, RoundingMode::Ceil), "3ms");
+    assert_eq!(fmt(999_000_001, RoundingMode::Ceil), "1s");
+    assert_eq!(fmt(999_999, RoundingMode::Truncate), "999µs");
+    assert_eq!(fmt(999_500, RoundingMode::HalfEven), "1ms");
+    assert_eq!(fmt(1, RoundingMode::Ceil), "1ns");
+
+    let style = TimeFormatStyle::new()
+        .significant_figures(3)
+        .rounding(RoundingMode::Ceil);
+    let sig = |nanos| format!("{}", TimeFormat::with_style(Duration::new(0, nanos), style));
+    assert_eq!(sig(2_000_001), "2.01ms");
+    assert_eq!(sig(999_001), "1.00ms");
+    assert_eq!(sig(1), "1.00ns");
+}
+
+#[test]
+fn other_formatters() {
+    let dur = Duration::new(3_599, 999_500_000);
+
+    let compound = |mode| format!("{:.0}", CompoundFormat::new(dur).rounding(mode));
+    assert_eq!(compound(RoundingMode::HalfUp), "1h 00m 0s");
+    assert_eq!(compound(RoundingMode::HalfEven), "1h 00m 0s");
+    assert_eq!(compound(RoundingMode::Truncate), "59m 59s");
+
+    let clock = |mode| format!("{:.2}", ClockFormat::new(dur).rounding(mode));
+    assert_eq!(clock(RoundingMode::HalfEven), "01:00:00.00");
+    assert_eq!(clock(RoundingMode::Floor), "59:59.99");
+
+    let template = DurationTemplate::new("%h:%M:%S").unwrap();
+    assert_eq!(template.format(dur).to_string(), "1:00:00");
+    let template = template.rounding(RoundingMode::Truncate);
+    assert_eq!(template.format(dur).to_string(), "0:59:59");
+
+    let dur = Duration::new(0, 1);
+    let template = DurationTemplate::new("%S.%3f")
+        .unwrap()
+        .rounding(RoundingMode::Ceil);
+    assert_eq!(template.format(dur).to_string(), "00.001");
+}
@@ -0,0 +1,87 @@
+extern crate floating_duration;
+
+mod common;
+
+use std::time::Duration;
+
+use floating_duration::{parse_duration, ShortestFormat};
+
+use common::Rng;
+
+fn fmt(secs: u64, nanos: u32) -> String {
+    format!("{}", ShortestFormat(Duration::new(secs, nanos)))
+}
+
+#[test]
+fn format() {
+    assert_eq!(fmt(0, 0), "0s");
+    assert_eq!(fmt(0, 1), "1ns");
+    assert_eq!(fmt(0, 999), "999ns");
+    assert_eq!(fmt(0, 1_000), "1µs");
+    assert_eq!(fmt(0, 1_500), "1.5µs");
+    assert_eq!(fmt(0, 1_001), "1001ns");
+    assert_eq!(fmt(0, 1_000_100), "1.0001ms");
+    assert_eq!(fmt(0, 999_999_999), "999999999ns");
+    assert_eq!(fmt(0, 999_999_000), "999999µs");
+    assert_eq!(fmt(0, 999_990_000), "999.99ms");
+    assert_eq!(fmt(1, 0), "1s");
+    assert_eq!(fmt(1, 1), "1.000000001s");
+    assert_eq!(fmt(20, 0), "20s");
+    assert_eq!(fmt(60, 0), "1m");
+    assert_eq!(fmt(150, 0), "2.5m");
+    assert_eq!(fmt(3_600, 0), "1h");
+    assert_eq!(fmt(86_400, 0), "1d");
+    assert_eq!(fmt(129_600, 0), "36h");
+    assert_eq!(fmt(1_296_000, 0), "15d");
+    assert_eq!(fmt(86_401, 0), "86401s");
+    assert_eq!(
+        fmt(u64::max_value(), 999_999_999),
+        "18446744073709551615.999999999s"
+    );
+
+    let dur = Duration::new(1, 500_000_000);
+    assert_eq!(format!("{:#}", ShortestFormat(dur)), "1.5 seconds");
+    assert_eq!(
+        format!("{:#}", ShortestFormat(Duration::new(60, 0))),
+        "1 minute"
+    );
+    assert_eq!(format!("[{:>6}]", ShortestFormat(dur)), "[  1.5s]");
+}
+
+#[test]
+fn round_trip() {
+    let mut rng = Rng::new(0x3c6e_f372_fe94_f82b);
+
+    for i in 0..20_000 {
+        let secs = rng.next_u64() >> (rng.next_u64() % 64);
+        let nanos = match i % 4 {
+            0 => 0,
+            1 => (rng.next_u64() % 1_000) as u32 * 1_000_000,
+            _ => (rng.next_u64() % 1_000_000_000) as u32,
+        };
+        let dur = Duration::new(secs, nanos);
+
+        let short = format!("{}", ShortestFormat(dur));
+        let ShortestFormat(parsed) = short.parse().unwrap();
+        assert_eq!(parsed, dur, "{}", short);
+        assert_eq!(parse_duration(&short), Ok(dur), "{}", short);
+
+        let long = format!("{:#}", ShortestFormat(dur));
+        let ShortestFormat(parsed) = long.parse().unwrap();
+        assert_eq!(parsed, dur, "{}", long);
+
+        // Nothing is longer than the exact seconds.
+        let mut exact = format!("{}.{:09}", secs, nanos);
+        while exact.contains('.') && (exact.ends_with('0') || exact.ends_with('.')) {
+            exact.pop();
+        }
+        assert!(short.chars().count() <= exact.len() + 1, "{}", short);
+    }
+}
+
+#[test]
+fn parse_errors() {
+    assert!("".parse::<ShortestFormat<Duration>>().is_err());
+    assert!("1h 2m".parse::<ShortestFormat<Duration>>().is_err());
+    assert!("1.5".parse::<ShortestFormat<Duration>>().is_err());
+}
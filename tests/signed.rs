@@ -0,0 +1,129 @@
+extern crate floating_duration;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use floating_duration::{
+    ParseErrorKind, RoundingMode, SignedDuration, SignedDurationSince, TimeAsFloat, TimeFormat,
+    TimeFormatStyle, TimeUnit,
+};
+
+fn neg(secs: u64, nanos: u32) -> SignedDuration {
+    SignedDuration::new(true, Duration::new(secs, nanos))
+}
+
+#[test]
+fn sign() {
+    let zero = SignedDuration::new(true, Duration::new(0, 0));
+    assert!(!zero.is_negative());
+    assert_eq!(zero, SignedDuration::default());
+    assert_eq!(-zero, zero);
+
+    let dur = neg(1, 0);
+    assert!(dur.is_negative());
+    assert!(!(-dur).is_negative());
+    assert_eq!(-(-dur), dur);
+
+    let mut values = vec![
+        SignedDuration::from(Duration::new(2, 0)),
+        neg(1, 0),
+        zero,
+        neg(2, 0),
+        SignedDuration::from(Duration::new(1, 0)),
+    ];
+    values.sort();
+    assert_eq!(
+        values,
+        [
+            neg(2, 0),
+            neg(1, 0),
+            zero,
+            SignedDuration::from(Duration::new(1, 0)),
+            SignedDuration::from(Duration::new(2, 0)),
+        ]
+    );
+}
+
+#[test]
+fn as_float() {
+    let dur = neg(4, 500_000_000);
+    assert_eq!(dur.as_fractional_secs(), -4.5);
+    assert_eq!(dur.as_fractional_millis(), -4_500.0);
+    assert_eq!(dur.as_fractional_nanos(), -4_500_000_000.0);
+    assert_eq!(dur.as_fractional(TimeUnit::Minutes), -0.075);
+    assert_eq!(dur.as_fractional_secs_f32(), -4.5);
+
+    let dur = SignedDuration::from(Duration::new(4, 500_000_000));
+    assert_eq!(dur.as_fractional_secs(), 4.5);
+}
+
+#[test]
+fn format() {
+    assert_eq!(format!("{}", neg(0, 1_234_000)), "-1.234ms");
+    assert_eq!(format!("{}", TimeFormat(neg(0, 5))), "-5ns");
+    assert_eq!(format!("{}", neg(0, 999_747)), "-999.747µs");
+    assert_eq!(format!("{:+}", neg(1, 0)), "-1s");
+    assert_eq!(
+        format!("{:+}", SignedDuration::from(Duration::new(1, 0))),
+        "+1s"
+    );
+    assert_eq!(format!("[{:>8.1}]", neg(1, 0)), "[   -1.0s]");
+    assert_eq!(format!("{:#}", neg(1, 0)), "-1 second");
+    assert_eq!(format!("{}", SignedDuration::default()), "0ns");
+
+    let style = TimeFormatStyle::new().unit(TimeUnit::Millis);
+    assert_eq!(
+        format!("{}", TimeFormat::with_style(neg(0, 400), style)),
+        "0ms"
+    );
+
+    // Rounding towards negative infinity moves away from zero.
+    let fmt = |dur, mode| {
+        let style = TimeFormatStyle::new().decimals(0).rounding(mode);
+
+        format!("{}", TimeFormat::with_style(dur, style))
+    };
+    let dur = neg(0, 2_500_000);
+    assert_eq!(fmt(dur, RoundingMode::Floor), "-3ms");
+    assert_eq!(fmt(dur, RoundingMode::Ceil), "-2ms");
+    assert_eq!(fmt(dur, RoundingMode::Truncate), "-2ms");
+    assert_eq!(fmt(dur, RoundingMode::HalfUp), "-3ms");
+    assert_eq!(fmt(dur, RoundingMode::HalfEven), "-2ms");
+}
+
+#[test]
+fn parse() {
+    assert_eq!("-1.5s".parse(), Ok(neg(1, 500_000_000)));
+    assert_eq!("- 1h 30m".parse(), Ok(neg(5_400, 0)));
+    assert_eq!(
+        "+2ms".parse(),
+        Ok(SignedDuration::from(Duration::new(0, 2_000_000)))
+    );
+    assert_eq!("-0s".parse(), Ok(SignedDuration::default()));
+
+    let err = "-".parse::<SignedDuration>().unwrap_err();
+    assert_eq!(err.kind(), ParseErrorKind::InvalidNumber);
+
+    for &dur in &[neg(0, 1), neg(1, 234_000_000), neg(3_723, 0)] {
+        let formatted = format!("{:.9}", dur);
+        assert_eq!(formatted.parse(), Ok(dur), "{}", formatted);
+    }
+}
+
+#[test]
+fn since() {
+    let epoch = UNIX_EPOCH;
+    let later = epoch + Duration::new(10, 0);
+
+    assert_eq!(
+        later.signed_duration_since(epoch),
+        SignedDuration::from(Duration::new(10, 0))
+    );
+    assert_eq!(epoch.signed_duration_since(later), neg(10, 0));
+    assert_eq!(
+        later.signed_duration_since(later),
+        SignedDuration::default()
+    );
+
+    let now = SystemTime::now();
+    assert!(!now.signed_duration_since(epoch).is_negative());
+}
@@ -0,0 +1,76 @@
+extern crate floating_duration;
+
+use std::time::Duration;
+
+use floating_duration::{DurationTemplate, TemplateErrorKind};
+
+fn fmt(pattern: &str, secs: u64, nanos: u32) -> String {
+    let template = DurationTemplate::new(pattern).unwrap();
+
+    template.format(Duration::new(secs, nanos)).to_string()
+}
+
+fn err(pattern: &str) -> (TemplateErrorKind, usize) {
+    let err = DurationTemplate::new(pattern).unwrap_err();
+
+    (err.kind(), err.offset())
+}
+
+#[test]
+fn directives() {
+    let dur = (93_784, 5_006_007);
+    assert_eq!(fmt("%dd %H:%M:%S", dur.0, dur.1), "1d 02:03:04");
+    assert_eq!(fmt("%h %m %s", dur.0, dur.1), "26 1563 93784");
+    assert_eq!(fmt("%L %U %N", dur.0, dur.1), "005 006 007");
+    assert_eq!(fmt("%l", 1, 5_000_000), "1005");
+    assert_eq!(fmt("%u", 0, 1_500), "2");
+    assert_eq!(fmt("%u|%n", 0, 1_500), "1|1500");
+    assert_eq!(
+        fmt("%f|%3f|%12f", 1, 5_000_000),
+        "005000000|005|005000000000"
+    );
+    assert_eq!(fmt("100%% done", 0, 0), "100% done");
+    assert_eq!(fmt("", 5, 0), "");
+}
+
+#[test]
+fn padding() {
+    assert_eq!(fmt("%-M:%S", 65, 0), "1:05");
+    assert_eq!(fmt("%_M:%S", 65, 0), " 1:05");
+    assert_eq!(fmt("%4s", 65, 0), "0065");
+    assert_eq!(fmt("%_4s|", 65, 0), "  65|");
+    assert_eq!(fmt("%1H", 3_600, 0), "1");
+    assert_eq!(
+        format!(
+            "[{:>8}]",
+            DurationTemplate::new("%M:%S")
+                .unwrap()
+                .format(Duration::new(65, 0))
+        ),
+        "[   01:05]"
+    );
+}
+
+#[test]
+fn rounding() {
+    assert_eq!(fmt("%M:%S.%3f", 59, 999_600_000), "01:00.000");
+    assert_eq!(fmt("%M:%S", 59, 500_000_000), "01:00");
+    assert_eq!(fmt("%M:%S", 59, 499_999_999), "00:59");
+    assert_eq!(fmt("%h", 5_399, 0), "1");
+    assert_eq!(fmt("%h", 5_400, 0), "2");
+    assert_eq!(fmt("%s.%f", 0, 1), "0.000000001");
+}
+
+#[test]
+fn errors() {
+    assert_eq!(err("%"), (TemplateErrorKind::MissingDirective, 1));
+    assert_eq!(err("%H:%3"), (TemplateErrorKind::MissingDirective, 5));
+    assert_eq!(err("%x"), (TemplateErrorKind::UnknownDirective, 1));
+    assert_eq!(err("µ%Q"), (TemplateErrorKind::UnknownDirective, 3));
+    assert_eq!(err("%-%"), (TemplateErrorKind::UnknownDirective, 2));
+    assert_eq!(err("%100s"), (TemplateErrorKind::InvalidWidth, 3));
+    assert_eq!(
+        DurationTemplate::new("%x").unwrap_err().to_string(),
+        "unknown directive at byte 1"
+    );
+}
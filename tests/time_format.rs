@@ -0,0 +1,405 @@
+extern crate floating_duration;
+
+mod common;
+
+use std::time::Duration;
+
+use floating_duration::{ParseErrorKind, RoundingMode, TimeFormat, TimeFormatStyle, TimeUnit};
+
+use common::{ns, Rng};
+
+/// Splits e.g. `"1.5ms"` into `(1.5, TimeUnit::Millis)`.
+fn split(formatted: &str) -> (f64, TimeUnit) {
+    let end = formatted
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap();
+    let unit = match &formatted[end..] {
+        "ns" => TimeUnit::Nanos,
+        "µs" => TimeUnit::Micros,
+        "ms" => TimeUnit::Millis,
+        "s" => TimeUnit::Secs,
+        "m" => TimeUnit::Minutes,
+        "h" => TimeUnit::Hours,
+        "d" => TimeUnit::Days,
+        other => panic!("unknown unit {:?}", other),
+    };
+
+    (formatted[..end].parse().unwrap(), unit)
+}
+
+/// The exclusive upper bound of a unit's range, if it isn't the largest.
+fn upper_bound(unit: TimeUnit, max_unit: TimeUnit) -> f64 {
+    if unit == max_unit {
+        return ::std::f64::INFINITY;
+    }
+
+    match unit {
+        TimeUnit::Nanos | TimeUnit::Micros | TimeUnit::Millis => 1_000.0,
+        TimeUnit::Secs | TimeUnit::Minutes => 60.0,
+        TimeUnit::Hours => 24.0,
+        TimeUnit::Days => ::std::f64::INFINITY,
+    }
+}
+
+fn check(nanos: u64, max_unit: TimeUnit, precision: Option<usize>) {
+    let style = TimeFormatStyle::new().max_unit(max_unit);
+    let format = TimeFormat::with_style(ns(nanos), style);
+    let formatted = match precision {
+        Some(p) => format!("{:.*}", p, format),
+        None => format!("{}", format),
+    };
+    let (value, unit) = split(&formatted);
+
+    assert!(unit <= max_unit, "{}: unit too large", formatted);
+    if unit != TimeUnit::Nanos {
+        assert!(value >= 1.0, "{} for {}ns: below 1", formatted, nanos);
+    }
+    assert!(
+        value < upper_bound(unit, max_unit),
+        "{} for {}ns: too large for the unit",
+        formatted,
+        nanos
+    );
+
+    let exact = nanos as f64 / unit.nanos() as f64;
+    let tolerance = 0.5 * 10f64.powi(-(precision.unwrap_or(3) as i32)) + 1e-9 * exact;
+    assert!(
+        (value - exact).abs() <= tolerance,
+        "{} for {}ns: wrongly rounded",
+        formatted,
+        nanos
+    );
+}
+
+#[test]
+fn boundaries() {
+    let boundaries: [u64; 6] = [
+        1_000,
+        1_000_000,
+        1_000_000_000,
+        60_000_000_000,
+        3_600_000_000_000,
+        86_400_000_000_000,
+    ];
+    let precisions = [None, Some(0), Some(1), Some(2), Some(3), Some(6), Some(9)];
+    let max_units = [TimeUnit::Secs, TimeUnit::Days];
+
+    for &boundary in &boundaries {
+        // Every value near the boundary itself, and near the points where
+        // rounding to 0 to 3 decimals in the smaller unit carries over.
+        let mut centers = vec![boundary];
+        for &step in &[1, 10, 100, 1_000, 10_000, 100_000, 1_000_000] {
+            if step * 2 < boundary {
+                centers.push(boundary - step / 2);
+            }
+        }
+
+        for &center in &centers {
+            for nanos in center.saturating_sub(3)..center + 3 {
+                for &max_unit in &max_units {
+                    for &precision in &precisions {
+                        check(nanos, max_unit, precision);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn small_values() {
+    for nanos in 0..20_000 {
+        check(nanos, TimeUnit::Secs, None);
+        check(nanos, TimeUnit::Secs, Some(1));
+    }
+}
+
+#[test]
+fn exact_boundaries() {
+    let fmt = |nanos| format!("{}", TimeFormat(ns(nanos)));
+
+    assert_eq!(fmt(0), "0ns");
+    assert_eq!(fmt(999), "999ns");
+    assert_eq!(fmt(1_000), "1µs");
+    assert_eq!(fmt(999_499), "999.499µs");
+    assert_eq!(fmt(999_500), "999.5µs");
+    assert_eq!(fmt(999_747), "999.747µs");
+    assert_eq!(fmt(999_999_600), "1s");
+    assert_eq!(fmt(9_999_996), "10ms");
+    assert_eq!(fmt(1_000_000), "1ms");
+    assert_eq!(fmt(999_499_999), "999.5ms");
+    assert_eq!(fmt(999_500_000), "999.5ms");
+    assert_eq!(fmt(999_600_000), "999.6ms");
+    assert_eq!(fmt(999_999_500), "1s");
+    assert_eq!(fmt(1_000_000_000), "1s");
+}
+
+#[test]
+fn rollover() {
+    let dur = Duration::new(0, 999_999);
+    assert_eq!(format!("{:.2}", TimeFormat(dur)), "1.00ms");
+    assert_eq!(format!("{:.3}", TimeFormat(dur)), "999.999µs");
+    assert_eq!(format!("{:.6}", TimeFormat(dur)), "999.999000µs");
+
+    let dur = Duration::new(0, 999_999_600);
+    assert_eq!(format!("{}", TimeFormat(dur)), "1s");
+
+    let style = TimeFormatStyle::new().max_unit(TimeUnit::Days);
+    let dur = Duration::new(3_599, 999_600_000);
+    assert_eq!(format!("{}", TimeFormat::with_style(dur, style)), "1h");
+    assert_eq!(format!("{:.4}", TimeFormat::with_style(dur, style)), "1.0000h");
+
+    let dur = Duration::new(3_599, 0);
+    assert_eq!(format!("{:.4}", TimeFormat::with_style(dur, style)), "59.9833m");
+
+    // Values which only round up to one of the next unit stay in their own.
+    let dur = Duration::new(0, 500_000_000);
+    assert_eq!(format!("{:.0}", TimeFormat(dur)), "500ms");
+    let dur = Duration::new(0, 950_000_000);
+    assert_eq!(format!("{:.1}", TimeFormat(dur)), "950.0ms");
+
+    let style = style.decimals(0);
+    let fmt = |secs| format!("{}", TimeFormat::with_style(Duration::new(secs, 0), style));
+    assert_eq!(fmt(1_800), "30m");
+    assert_eq!(fmt(43_200), "12h");
+    assert_eq!(fmt(86_399), "1d");
+}
+
+#[test]
+fn style() {
+    let fmt = |nanos, style| format!("{}", TimeFormat::with_style(ns(nanos), style));
+
+    for &nanos in &[0, 1, 999, 1_500, 999_999, 1_234_567, 59_999_999_999] {
+        assert_eq!(
+            fmt(nanos, TimeFormatStyle::default()),
+            format!("{}", TimeFormat(ns(nanos)))
+        );
+    }
+
+    let style = TimeFormatStyle::new().min_unit(TimeUnit::Millis);
+    assert_eq!(fmt(1_500, style), "0.002ms");
+    assert_eq!(fmt(400, style), "0ms");
+    assert_eq!(fmt(1_500_000, style), "1.5ms");
+
+    let style = TimeFormatStyle::new().min_unit(TimeUnit::Hours);
+    assert_eq!(fmt(60_000_000_000, style), "0.017h");
+
+    let style = TimeFormatStyle::new().decimals(0);
+    assert_eq!(fmt(1_500_000, style), "2ms");
+    assert_eq!(fmt(999_600, style), "1ms");
+
+    let style = TimeFormatStyle::new().strip_zeros(false);
+    assert_eq!(fmt(1_500_000, style), "1.500ms");
+    assert_eq!(fmt(15, style), "15.000ns");
+
+    let style = TimeFormatStyle::new().rounding(RoundingMode::Truncate);
+    assert_eq!(fmt(999_999, style), "999.999µs");
+    assert_eq!(fmt(999_999_999, style), "999.999ms");
+
+    let style = TimeFormatStyle::new().ascii(true).space(true);
+    assert_eq!(fmt(1_500, style), "1.5 us");
+    assert_eq!(
+        format!("{:#}", TimeFormat::with_style(ns(1_500), style)),
+        "1.5 microseconds"
+    );
+    let parsed: TimeFormat<Duration> = fmt(1_500, style).parse().unwrap();
+    assert_eq!(parsed.0, ns(1_500));
+
+    let style = TimeFormatStyle::new().long_names(true);
+    assert_eq!(fmt(1_000_000, style), "1 millisecond");
+}
+
+#[test]
+fn fixed_unit() {
+    let fmt = |nanos, unit| format!("{}", TimeFormat::in_unit(ns(nanos), unit));
+
+    assert_eq!(fmt(0, TimeUnit::Millis), "0ms");
+    assert_eq!(fmt(980_000, TimeUnit::Millis), "0.98ms");
+    assert_eq!(fmt(499, TimeUnit::Millis), "0ms");
+    assert_eq!(fmt(3_723_000_000_000, TimeUnit::Millis), "3723000ms");
+    assert_eq!(fmt(90_000_000_000, TimeUnit::Hours), "0.025h");
+    assert_eq!(fmt(1_500, TimeUnit::Nanos), "1500ns");
+
+    let dur = ns(1_000_000);
+    assert_eq!(
+        format!("{:#}", TimeFormat::in_unit(dur, TimeUnit::Secs)),
+        "0.001 seconds"
+    );
+    assert_eq!(
+        format!("{:>9.4}", TimeFormat::in_unit(dur, TimeUnit::Secs)),
+        "  0.0010s"
+    );
+
+    let style = TimeFormatStyle::new().unit(TimeUnit::Micros).ascii(true);
+    assert_eq!(format!("{}", TimeFormat::with_style(dur, style)), "1000us");
+
+    // The precision isn't limited to nanoseconds.
+    let fmt = |nanos, unit, precision| {
+        let dur = ns(nanos);
+
+        format!("{:.*}", precision, TimeFormat::in_unit(dur, unit))
+    };
+    assert_eq!(fmt(1_000_000_000, TimeUnit::Minutes, 12), "0.016666666667m");
+    assert_eq!(fmt(1_000_000_000, TimeUnit::Hours, 14), "0.00027777777778h");
+    assert_eq!(fmt(1, TimeUnit::Hours, 20), "0.00000000000027777778h");
+    assert_eq!(fmt(1, TimeUnit::Days, 18), "0.000000000000011574d");
+    assert_eq!(
+        fmt(1_000_000_000, TimeUnit::Millis, 12),
+        "1000.000000000000ms"
+    );
+    assert_eq!(
+        fmt(5_000_000_000, TimeUnit::Minutes, 45),
+        "0.083333333333333333333333333333333333333333333m"
+    );
+
+    let dur = Duration::new(u64::max_value(), 999_999_999);
+    assert_eq!(
+        format!("{:.30}", TimeFormat::in_unit(dur, TimeUnit::Days)),
+        "213503982334601.291851851851840277777777777778d"
+    );
+}
+
+#[test]
+fn significant_figures() {
+    let fmt = |nanos, figures| {
+        let style = TimeFormatStyle::new().significant_figures(figures);
+
+        format!("{}", TimeFormat::with_style(ns(nanos), style))
+    };
+
+    assert_eq!(fmt(123_456_789, 4), "123.5ms");
+    assert_eq!(fmt(1_234, 4), "1.234µs");
+    assert_eq!(fmt(12_345_678_901, 4), "12.35s");
+    assert_eq!(fmt(1_000_000, 4), "1.000ms");
+    assert_eq!(fmt(5, 4), "5.000ns");
+    assert_eq!(fmt(0, 4), "0ns");
+    assert_eq!(fmt(123_456_789, 2), "120ms");
+    assert_eq!(fmt(123_456_789, 0), "100ms");
+
+    // Carries within a unit and into the next one.
+    assert_eq!(fmt(9_999_600, 4), "10.00ms");
+    assert_eq!(fmt(999_960, 4), "1.000ms");
+    assert_eq!(fmt(999_600, 3), "1.00ms");
+    assert_eq!(fmt(999_949, 4), "999.9µs");
+    assert_eq!(fmt(999_999_999, 9), "999.999999ms");
+    assert_eq!(fmt(999_999_999, 8), "1.0000000s");
+
+    // Values below the smallest unit.
+    let style = TimeFormatStyle::new()
+        .significant_figures(3)
+        .min_unit(TimeUnit::Secs);
+    let fmt = |nanos| format!("{}", TimeFormat::with_style(ns(nanos), style));
+    assert_eq!(fmt(1_234_567), "0.00123s");
+    assert_eq!(fmt(9_996_000), "0.0100s");
+    assert_eq!(fmt(1), "0.00000000100s");
+
+    // Units which aren't a power of ten have more than nine decimal places.
+    let fmt = |nanos, style| format!("{}", TimeFormat::with_style(ns(nanos), style));
+    let style = TimeFormatStyle::new()
+        .unit(TimeUnit::Minutes)
+        .significant_figures(4);
+    assert_eq!(fmt(1, style), "0.00000000001667m");
+    assert_eq!(fmt(7, style), "0.0000000001167m");
+    assert_eq!(fmt(59_999_999_999, style), "1.000m");
+    let style = TimeFormatStyle::new()
+        .max_unit(TimeUnit::Days)
+        .significant_figures(12);
+    assert_eq!(fmt(100_000_000_000, style), "1.66666666667m");
+    assert_eq!(fmt(100_000_000_000_000, style), "1.15740740741d");
+    let style = TimeFormatStyle::new()
+        .min_unit(TimeUnit::Hours)
+        .max_unit(TimeUnit::Days)
+        .significant_figures(15);
+    assert_eq!(fmt(1, style), "0.000000000000277777777777778h");
+    assert_eq!(fmt(1_000, style), "0.000000000277777777777778h");
+    let style = TimeFormatStyle::new()
+        .min_unit(TimeUnit::Days)
+        .significant_figures(3)
+        .rounding(RoundingMode::Floor);
+    assert_eq!(fmt(1, style), "0.0000000000000115d");
+
+    let style = TimeFormatStyle::new().significant_figures(4);
+    let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+    for _ in 0..10_000 {
+        let random = rng.next_u64();
+        let nanos = (random % 999_000_000_000) >> (random % 40);
+        if nanos == 0 {
+            continue;
+        }
+
+        let formatted = format!("{}", TimeFormat::with_style(ns(nanos), style));
+        let (value, unit) = split(&formatted);
+        let figures = formatted.chars().filter(char::is_ascii_digit).count();
+        assert_eq!(figures, 4, "{}", formatted);
+        assert!(value >= 1.0 && value < 1000.0, "{}", formatted);
+
+        let exact = nanos as f64 / unit.nanos() as f64;
+        let scale = 10f64.powi(3 - value.log10().floor() as i32);
+        assert!(
+            (exact - value).abs() <= 0.5 / scale * (1.0 + 1e-9),
+            "{}",
+            formatted
+        );
+    }
+
+    // An explicit precision takes precedence.
+    let style = TimeFormatStyle::new().significant_figures(4);
+    let dur = ns(123_456_789);
+    assert_eq!(
+        format!("{:.1}", TimeFormat::with_style(dur, style)),
+        "123.5ms"
+    );
+    assert_eq!(
+        format!("{:.0}", TimeFormat::with_style(dur, style)),
+        "123ms"
+    );
+}
+
+#[test]
+fn parse_round_trip() {
+    let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..10_000 {
+        let dur = ns(rng.next_u64() >> (rng.next_u64() % 64));
+
+        for formatted in &[
+            format!("{:.9}", TimeFormat(dur)),
+            format!("{:#.9}", TimeFormat(dur)),
+            format!("{:+15.9}", TimeFormat(dur)),
+        ] {
+            let TimeFormat(parsed) = formatted.parse().unwrap();
+            assert_eq!(parsed, dur, "{}", formatted);
+        }
+
+        let formatted = format!("{}", TimeFormat(dur));
+        let TimeFormat(parsed) = formatted.parse().unwrap();
+        let (value, unit) = split(&formatted);
+        let diff = (parsed.as_secs() as f64 - dur.as_secs() as f64) * 1e9
+            + (parsed.subsec_nanos() as f64 - dur.subsec_nanos() as f64);
+        assert!(diff.abs() <= 0.0005 * unit.nanos() as f64 + 1e-9 * value, "{}", formatted);
+    }
+}
+
+#[test]
+fn parse_forms() {
+    let parse = |s: &str| s.parse::<TimeFormat<Duration>>().map(|f| f.0);
+
+    assert_eq!(parse("461.93µs"), Ok(Duration::new(0, 461_930)));
+    assert_eq!(parse("461.93us"), Ok(Duration::new(0, 461_930)));
+    assert_eq!(parse("461.93 microseconds"), Ok(Duration::new(0, 461_930)));
+    assert_eq!(parse("1 microsecond"), Ok(Duration::new(0, 1_000)));
+    assert_eq!(parse("0ns"), Ok(Duration::new(0, 0)));
+    assert_eq!(parse("1.5h"), Ok(Duration::new(5_400, 0)));
+    assert_eq!(parse("2 days"), Ok(Duration::new(172_800, 0)));
+    assert_eq!(parse("0.0000000015s"), Ok(Duration::new(0, 2)));
+    assert_eq!(parse("  +3ms  "), Ok(Duration::new(0, 3_000_000)));
+
+    let err = |s: &str| parse(s).map_err(|e| (e.kind(), e.offset())).unwrap_err();
+
+    assert_eq!(err(""), (ParseErrorKind::Empty, 0));
+    assert_eq!(err("ms"), (ParseErrorKind::InvalidNumber, 0));
+    assert_eq!(err("1.5"), (ParseErrorKind::MissingUnit, 3));
+    assert_eq!(err("1.5 fortnights"), (ParseErrorKind::UnknownUnit, 4));
+    assert_eq!(err("1.5ms!"), (ParseErrorKind::UnexpectedCharacter, 5));
+    assert_eq!(err("18446744073709551616s"), (ParseErrorKind::OutOfRange, 0));
+}